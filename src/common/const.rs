@@ -1 +1,138 @@
 pub(crate) const SERVER_TYPE_KEY: &str = "Server";
+
+pub(crate) const REDIRECT_STATUS_CODES: [u16; 5] = [301, 302, 303, 307, 308];
+
+pub(crate) const COOKIE_KEY_OPTION: &str = "cookie_key";
+
+pub(crate) const COOKIE_KEY_MIN_SIZE: usize = 64;
+
+pub(crate) const GUARD_RESPOND_OPTION: &str = "respond";
+
+pub(crate) const CAPTURE_OPTION: &str = "capture";
+
+pub(crate) const GUARD_METHODS: [&str; 10] = [
+    "get", "post", "put", "delete", "patch", "head", "options", "connect", "trace", "unknown",
+];
+
+pub(crate) const GUARD_VERSIONS: [&str; 8] = [
+    "http0_9",
+    "http1_0",
+    "http1_1",
+    "http2",
+    "http3",
+    "http1_1_or_higher",
+    "http",
+    "unknown",
+];
+
+pub(crate) const GUARD_UPGRADE_TYPES: [&str; 4] = ["ws", "h2c", "tls", "unknown"];
+
+pub(crate) const REFERER_ORIGIN_MODE: &str = "origin";
+
+pub(crate) const REFERER_PREFIX_MODE: &str = "prefix";
+
+pub(crate) const ORIGIN_MISSING_OPTION: &str = "missing";
+
+pub(crate) const ORIGIN_MISSING_ALLOW: &str = "allow";
+
+pub(crate) const ORIGIN_MISSING_DENY: &str = "deny";
+
+pub(crate) const IP_RANGES_OPTION: &str = "ranges";
+
+pub(crate) const IP_TRUSTED_PROXIES_OPTION: &str = "trusted_proxies";
+
+pub(crate) const RATE_LIMIT_PER_OPTION: &str = "per";
+
+pub(crate) const RATE_LIMIT_KEY_OPTION: &str = "key";
+
+pub(crate) const RATE_LIMIT_MAX_KEYS_OPTION: &str = "max_keys";
+
+pub(crate) const RATE_LIMIT_CLIENT_IP_KEY: &str = "client_ip";
+
+pub(crate) const RATE_LIMIT_DEFAULT_MAX_KEYS: usize = 10_000;
+
+pub(crate) const RATE_LIMIT_STATUS_CODE: usize = 429;
+
+pub(crate) const RETRY_AFTER_HEADER: &str = "retry-after";
+
+pub(crate) const RATE_LIMIT_LIMIT_HEADER: &str = "ratelimit-limit";
+
+pub(crate) const RATE_LIMIT_REMAINING_HEADER: &str = "ratelimit-remaining";
+
+pub(crate) const RATE_LIMIT_RESET_HEADER: &str = "ratelimit-reset";
+
+pub(crate) const UNSUPPORTED_MEDIA_TYPE_STATUS_CODE: usize = 415;
+
+pub(crate) const NOT_ACCEPTABLE_STATUS_CODE: usize = 406;
+
+pub(crate) const PAYLOAD_TOO_LARGE_STATUS_CODE: usize = 413;
+
+pub(crate) const REQUEST_BODY_EXTRACTOR_MACROS: [&str; 3] = [
+    "request_body",
+    "request_body_json",
+    "request_body_json_result",
+];
+
+pub(crate) const BAD_REQUEST_STATUS_CODE: usize = 400;
+
+pub(crate) const SWITCHING_PROTOCOLS_STATUS_CODE: usize = 101;
+
+pub(crate) const WEBSOCKET_VERSION: &str = "13";
+
+pub(crate) const WS_UPGRADE_PROTOCOLS_OPTION: &str = "protocols";
+
+pub(crate) const WS_UNSUPPORTED_PROTOCOL_BODY: &str = "unsupported websocket subprotocol";
+
+pub(crate) const STREAM_ON_DECODE_ERROR_OPTION: &str = "on_decode_error";
+
+pub(crate) const DECODE_ERROR_SKIP: &str = "skip";
+
+pub(crate) const DECODE_ERROR_CLOSE: &str = "close";
+
+pub(crate) const DECODE_ERROR_RESPOND: &str = "respond";
+
+pub(crate) const WS_CLOSE_INVALID_PAYLOAD_FRAME: [u8; 4] = [0x88, 0x02, 0x03, 0xEF];
+
+pub(crate) const STREAM_PING_INTERVAL_OPTION: &str = "ping_interval";
+
+pub(crate) const STREAM_IDLE_TIMEOUT_OPTION: &str = "idle_timeout";
+
+pub(crate) const STREAM_ON_CLOSE_OPTION: &str = "on_close";
+
+pub(crate) const STREAM_ON_ERROR_OPTION: &str = "on_error";
+
+pub(crate) const STREAM_MAX_REQUESTS_OPTION: &str = "max_requests";
+
+pub(crate) const WS_BROADCAST_EXCLUDE_OPTION: &str = "exclude";
+
+pub(crate) const WS_JOIN_MACRO: &str = "ws_join";
+
+pub(crate) const WS_ROOM_OUTBOX_CAPACITY: usize = 64;
+
+pub(crate) const TRY_GET_WEBSOCKET_REQUEST_MACRO: &str = "try_get_websocket_request";
+
+pub(crate) const WS_DISPATCH_TAG_OPTION: &str = "tag";
+
+pub(crate) const WS_DISPATCH_DEFAULT_TAG: &str = "type";
+
+pub(crate) const WS_DISPATCH_ON_ATTRIBUTE: &str = "on";
+
+pub(crate) const WS_DISPATCH_FALLBACK_ATTRIBUTE: &str = "fallback";
+
+pub(crate) const CACHE_CONTROL_HEADER: &str = "cache-control";
+
+pub(crate) const SSE_COMMENT_OPTION: &str = "comment";
+
+pub(crate) const SSE_EVENT_OPTION: &str = "event";
+
+pub(crate) const SSE_ID_OPTION: &str = "id";
+
+pub(crate) const SSE_RETRY_OPTION: &str = "retry";
+
+pub(crate) const SSE_DATA_OPTION: &str = "data";
+
+pub(crate) const SSE_KEEP_ALIVE_OPTION: &str = "keep_alive";
+
+pub(crate) const SSE_DEFAULT_KEEP_ALIVE_MILLIS: u64 = 15_000;
+
+pub(crate) const SSE_KEEP_ALIVE_COMMENT: &str = ": keep-alive\n\n";
@@ -355,3 +355,186 @@ pub(crate) fn leak_context(is_unsafe_error: bool, context: &Ident) -> proc_macro
         }
     }
 }
+
+/// Generates a token stream that emits a compiler warning at the given span.
+///
+/// Stable proc macros cannot emit warnings directly, so this declares a deprecated
+/// unit struct and uses it, which makes rustc report the note as a deprecation warning.
+///
+/// # Arguments
+///
+/// - `Span` - The span the warning should point at.
+/// - `&str` - The warning message.
+///
+/// # Returns
+///
+/// - `TokenStream2` - The token stream producing the warning.
+pub(crate) fn compile_warning(span: Span, note: &str) -> proc_macro2::TokenStream {
+    let marker: Ident = Ident::new("HyperlaneMacrosWarning", span);
+    quote! {
+        {
+            #[deprecated(note = #note)]
+            #[allow(non_camel_case_types, dead_code)]
+            struct HyperlaneMacrosWarning;
+            let _ = #marker;
+        }
+    }
+}
+
+/// Finds the literal argument of another attribute on the same function.
+///
+/// # Arguments
+///
+/// - `&TokenStream` - The function token stream to search.
+/// - `&str` - The attribute name to look for.
+///
+/// # Returns
+///
+/// - `Option<Lit>` - The literal argument, if the attribute is present and takes a single literal.
+pub(crate) fn find_attribute_literal(item: &TokenStream, name: &str) -> Option<Lit> {
+    let input_fn: ItemFn = parse(item.clone()).ok()?;
+    input_fn
+        .attrs
+        .iter()
+        .filter(|attr: &&Attribute| attr.path().is_ident(name))
+        .find_map(|attr: &Attribute| attr.parse_args::<Lit>().ok())
+}
+
+/// Checks whether a function carries an attribute with the given name.
+///
+/// The last path segment is compared, so `max_body_size` and `hyperlane_macros::max_body_size` match.
+///
+/// # Arguments
+///
+/// - `&[Attribute]` - The function attributes.
+/// - `&str` - The attribute name to look for.
+///
+/// # Returns
+///
+/// - `bool` - Whether the attribute is present.
+pub(crate) fn has_attribute(attrs: &[Attribute], name: &str) -> bool {
+    attrs.iter().any(|attr: &Attribute| {
+        attr.path()
+            .segments
+            .last()
+            .is_some_and(|segment: &PathSegment| segment.ident == name)
+    })
+}
+
+/// Checks whether the input continues with a guard response option.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `bool` - `true` if the next tokens are `respond =` or `else =`.
+pub(crate) fn peek_guard_response(input: ParseStream) -> bool {
+    let fork: ParseStream = &input.fork();
+    let is_option: bool = if fork.peek(Token![else]) {
+        fork.parse::<Token![else]>().is_ok()
+    } else {
+        fork.parse::<Ident>()
+            .is_ok_and(|name: Ident| name == GUARD_RESPOND_OPTION)
+    };
+    is_option && fork.peek(Token![=]) && !fork.peek(Token![==]) && !fork.peek(Token![=>])
+}
+
+/// Parses the optional guard response of a guard macro without other arguments.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+///
+/// # Returns
+///
+/// - `syn::Result<Option<GuardResponse>>` - `None` for an empty attribute, otherwise the parsed response.
+pub(crate) fn parse_guard_response(attr: TokenStream) -> syn::Result<Option<GuardResponse>> {
+    if attr.is_empty() {
+        return Ok(None);
+    }
+    parse::<GuardResponse>(attr).map(Some)
+}
+
+/// Generates the early return of a guard whose check failed.
+///
+/// Without a guard response this returns `Status::Continue` without responding. With one,
+/// it sets the status code, its reason phrase and the body, sends the response and
+/// returns `Status::Reject` so no later hook sends again.
+///
+/// # Arguments
+///
+/// - `&Ident` - The context identifier.
+/// - `&Ident` - The stream identifier.
+/// - `Option<&GuardResponse>` - The optional guard response.
+///
+/// # Returns
+///
+/// - `TokenStream2` - The early return statement.
+pub(crate) fn guard_return(
+    context: &Ident,
+    stream: &Ident,
+    response: Option<&GuardResponse>,
+) -> proc_macro2::TokenStream {
+    let Some(response) = response else {
+        return quote! {
+            return ::hyperlane::Status::Continue;
+        };
+    };
+    let new_context: proc_macro2::TokenStream = leak_mut_context(false, context);
+    let status_code: &Expr = &response.status_code;
+    let body: proc_macro2::TokenStream = match &response.body {
+        Some(body) => quote! { .set_body(&#body) },
+        None => quote! { .set_body(::hyperlane::ResponseBody::new()) },
+    };
+    quote! {
+        {
+            let status_code: ::hyperlane::ResponseStatusCode = ::hyperlane::ResponseStatusCode::from(#status_code as usize);
+            let response: &mut ::hyperlane::Response = #new_context.get_mut_response();
+            response
+                .set_status_code(status_code)
+                .set_reason_phrase(::hyperlane::HttpStatus::phrase(status_code))
+                #body;
+            let _: ::std::result::Result<(), ::hyperlane::ResponseError> = #stream.try_send(response.build()).await;
+            return ::hyperlane::Status::Reject;
+        }
+    }
+}
+
+/// Parses a duration such as `"500ms"`, `"30s"`, `"1m"`, `"1h"` or `"1d"`.
+///
+/// # Arguments
+///
+/// - `&LitStr` - The duration literal.
+///
+/// # Returns
+///
+/// - `syn::Result<u64>` - The duration in milliseconds, or an error spanned at the literal.
+pub(crate) fn parse_duration_millis(duration: &LitStr) -> syn::Result<u64> {
+    let value: String = duration.value();
+    let split: usize = value
+        .find(|character: char| !character.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let unit_millis: Option<u64> = match unit {
+        "ms" => Some(1),
+        "s" => Some(1_000),
+        "m" => Some(60_000),
+        "h" => Some(3_600_000),
+        "d" => Some(86_400_000),
+        _ => None,
+    };
+    amount
+        .parse::<u64>()
+        .ok()
+        .zip(unit_millis)
+        .and_then(|(amount, unit_millis)| amount.checked_mul(unit_millis))
+        .filter(|millis: &u64| *millis > 0)
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                duration,
+                "expected a positive duration with a `ms`, `s`, `m`, `h` or `d` unit, such as \"1m\"",
+            )
+        })
+}
@@ -23,3 +23,41 @@ impl Parse for OrderAttr {
         Ok(OrderAttr { order: Some(expr) })
     }
 }
+
+/// Parses a guard response option.
+///
+/// Accepts `respond = code`, `respond = (code, body)` or the same forms with `else`
+/// in place of `respond`. Literal status codes are validated.
+impl Parse for GuardResponse {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![else]) {
+            input.parse::<Token![else]>()?;
+        } else {
+            let name: Ident = input.parse()?;
+            if name != GUARD_RESPOND_OPTION {
+                return Err(syn::Error::new_spanned(
+                    &name,
+                    "expected `respond = ...` or `else = ...`",
+                ));
+            }
+        }
+        input.parse::<Token![=]>()?;
+        let value: Expr = input.parse()?;
+        let (status_code, body): (Expr, Option<Expr>) = match value {
+            Expr::Tuple(tuple) if tuple.elems.len() == 2 => {
+                let mut elems = tuple.elems.into_iter();
+                (elems.next().unwrap(), elems.next())
+            }
+            Expr::Tuple(tuple) => {
+                return Err(syn::Error::new_spanned(
+                    tuple,
+                    "expected `code` or `(code, body)`",
+                ));
+            }
+            Expr::Paren(paren) => (*paren.expr, None),
+            value => (value, None),
+        };
+        validate_status_code(&status_code)?;
+        Ok(GuardResponse { status_code, body })
+    }
+}
@@ -12,6 +12,10 @@ pub(crate) static INJECTABLE_MACROS: &[InjectableMacro] = &[
         name: "filter",
         handler: Handler::WithAttrPosition(filter_macro),
     },
+    InjectableMacro {
+        name: "guard",
+        handler: Handler::WithAttrPosition(guard_macro),
+    },
     InjectableMacro {
         name: "try_flush",
         handler: Handler::NoAttrPosition(try_flush_macro),
@@ -20,6 +24,10 @@ pub(crate) static INJECTABLE_MACROS: &[InjectableMacro] = &[
         name: "flush",
         handler: Handler::NoAttrPosition(flush_macro),
     },
+    InjectableMacro {
+        name: "sse",
+        handler: Handler::NoAttrPosition(sse_macro),
+    },
     InjectableMacro {
         name: "task_panic",
         handler: Handler::WithAttr(task_panic_macro),
@@ -52,45 +60,81 @@ pub(crate) static INJECTABLE_MACROS: &[InjectableMacro] = &[
         name: "methods",
         handler: Handler::WithAttrPosition(methods_macro),
     },
+    InjectableMacro {
+        name: "consumes",
+        handler: Handler::WithAttrPosition(consumes_macro),
+    },
+    InjectableMacro {
+        name: "produces",
+        handler: Handler::WithAttrPosition(produces_macro),
+    },
+    InjectableMacro {
+        name: "max_body_size",
+        handler: Handler::WithAttrPosition(max_body_size_macro),
+    },
+    InjectableMacro {
+        name: "require_header",
+        handler: Handler::WithAttrPosition(require_header_macro),
+    },
+    InjectableMacro {
+        name: "header_eq",
+        handler: Handler::WithAttrPosition(header_eq_macro),
+    },
+    InjectableMacro {
+        name: "header_matches",
+        handler: Handler::WithAttrPosition(header_matches_macro),
+    },
+    InjectableMacro {
+        name: "require_query",
+        handler: Handler::WithAttrPosition(require_query_macro),
+    },
+    InjectableMacro {
+        name: "query_eq",
+        handler: Handler::WithAttrPosition(query_eq_macro),
+    },
+    InjectableMacro {
+        name: "query_matches",
+        handler: Handler::WithAttrPosition(query_matches_macro),
+    },
     InjectableMacro {
         name: "is_get_method",
-        handler: Handler::NoAttrPosition(is_get_method_handler),
+        handler: Handler::WithAttrPosition(is_get_method_handler),
     },
     InjectableMacro {
         name: "is_post_method",
-        handler: Handler::NoAttrPosition(is_post_method_handler),
+        handler: Handler::WithAttrPosition(is_post_method_handler),
     },
     InjectableMacro {
         name: "is_put_method",
-        handler: Handler::NoAttrPosition(is_put_method_handler),
+        handler: Handler::WithAttrPosition(is_put_method_handler),
     },
     InjectableMacro {
         name: "is_delete_method",
-        handler: Handler::NoAttrPosition(is_delete_method_handler),
+        handler: Handler::WithAttrPosition(is_delete_method_handler),
     },
     InjectableMacro {
         name: "is_patch_method",
-        handler: Handler::NoAttrPosition(is_patch_method_handler),
+        handler: Handler::WithAttrPosition(is_patch_method_handler),
     },
     InjectableMacro {
         name: "is_head_method",
-        handler: Handler::NoAttrPosition(is_head_method_handler),
+        handler: Handler::WithAttrPosition(is_head_method_handler),
     },
     InjectableMacro {
         name: "is_options_method",
-        handler: Handler::NoAttrPosition(is_options_method_handler),
+        handler: Handler::WithAttrPosition(is_options_method_handler),
     },
     InjectableMacro {
         name: "is_connect_method",
-        handler: Handler::NoAttrPosition(is_connect_method_handler),
+        handler: Handler::WithAttrPosition(is_connect_method_handler),
     },
     InjectableMacro {
         name: "is_trace_method",
-        handler: Handler::NoAttrPosition(is_trace_method_handler),
+        handler: Handler::WithAttrPosition(is_trace_method_handler),
     },
     InjectableMacro {
         name: "is_unknown_method",
-        handler: Handler::NoAttrPosition(is_unknown_method_handler),
+        handler: Handler::WithAttrPosition(is_unknown_method_handler),
     },
     InjectableMacro {
         name: "referer",
@@ -100,6 +144,22 @@ pub(crate) static INJECTABLE_MACROS: &[InjectableMacro] = &[
         name: "reject_referer",
         handler: Handler::WithAttrPosition(reject_referer_macro),
     },
+    InjectableMacro {
+        name: "origin",
+        handler: Handler::WithAttrPosition(origin_macro),
+    },
+    InjectableMacro {
+        name: "allow_ip",
+        handler: Handler::WithAttrPosition(allow_ip_macro),
+    },
+    InjectableMacro {
+        name: "deny_ip",
+        handler: Handler::WithAttrPosition(deny_ip_macro),
+    },
+    InjectableMacro {
+        name: "rate_limit",
+        handler: Handler::WithAttrPosition(rate_limit_macro),
+    },
     InjectableMacro {
         name: "reject",
         handler: Handler::WithAttrPosition(reject_macro),
@@ -192,6 +252,14 @@ pub(crate) static INJECTABLE_MACROS: &[InjectableMacro] = &[
         name: "request_cookies",
         handler: Handler::WithAttrPosition(request_cookies_macro),
     },
+    InjectableMacro {
+        name: "request_signed_cookie",
+        handler: Handler::WithAttrPosition(request_signed_cookie_macro),
+    },
+    InjectableMacro {
+        name: "response_signed_cookie",
+        handler: Handler::WithAttrPosition(response_signed_cookie_macro),
+    },
     InjectableMacro {
         name: "request_version",
         handler: Handler::WithAttrPosition(request_version_macro),
@@ -216,6 +284,14 @@ pub(crate) static INJECTABLE_MACROS: &[InjectableMacro] = &[
         name: "response_header",
         handler: Handler::WithAttrPosition(response_header_macro),
     },
+    InjectableMacro {
+        name: "remove_response_header",
+        handler: Handler::WithAttrPosition(remove_response_header_macro),
+    },
+    InjectableMacro {
+        name: "response_header_if_absent",
+        handler: Handler::WithAttrPosition(response_header_if_absent_macro),
+    },
     InjectableMacro {
         name: "response_body",
         handler: Handler::WithAttrPosition(response_body_macro),
@@ -244,6 +320,14 @@ pub(crate) static INJECTABLE_MACROS: &[InjectableMacro] = &[
         name: "send",
         handler: Handler::WithAttrPosition(send_macro),
     },
+    InjectableMacro {
+        name: "respond",
+        handler: Handler::WithAttrPosition(respond_macro),
+    },
+    InjectableMacro {
+        name: "redirect",
+        handler: Handler::WithAttrPosition(redirect_macro),
+    },
     InjectableMacro {
         name: "try_get_http_request",
         handler: Handler::WithAttr(try_get_http_request_macro),
@@ -254,50 +338,54 @@ pub(crate) static INJECTABLE_MACROS: &[InjectableMacro] = &[
     },
     InjectableMacro {
         name: "is_ws_upgrade_type",
-        handler: Handler::NoAttrPosition(is_ws_upgrade_type_macro),
+        handler: Handler::WithAttrPosition(is_ws_upgrade_type_macro),
+    },
+    InjectableMacro {
+        name: "ws_upgrade",
+        handler: Handler::WithAttrPosition(ws_upgrade_macro),
     },
     InjectableMacro {
         name: "is_h2c_upgrade_type",
-        handler: Handler::NoAttrPosition(is_h2c_upgrade_type_macro),
+        handler: Handler::WithAttrPosition(is_h2c_upgrade_type_macro),
     },
     InjectableMacro {
         name: "is_tls_upgrade_type",
-        handler: Handler::NoAttrPosition(is_tls_upgrade_type_macro),
+        handler: Handler::WithAttrPosition(is_tls_upgrade_type_macro),
     },
     InjectableMacro {
         name: "is_unknown_upgrade_type",
-        handler: Handler::NoAttrPosition(is_unknown_upgrade_type_macro),
+        handler: Handler::WithAttrPosition(is_unknown_upgrade_type_macro),
     },
     InjectableMacro {
         name: "is_http0_9_version",
-        handler: Handler::NoAttrPosition(is_http0_9_version_macro),
+        handler: Handler::WithAttrPosition(is_http0_9_version_macro),
     },
     InjectableMacro {
         name: "is_http1_0_version",
-        handler: Handler::NoAttrPosition(is_http1_0_version_macro),
+        handler: Handler::WithAttrPosition(is_http1_0_version_macro),
     },
     InjectableMacro {
         name: "is_http1_1_version",
-        handler: Handler::NoAttrPosition(is_http1_1_version_macro),
+        handler: Handler::WithAttrPosition(is_http1_1_version_macro),
     },
     InjectableMacro {
         name: "is_http2_version",
-        handler: Handler::NoAttrPosition(is_http2_version_macro),
+        handler: Handler::WithAttrPosition(is_http2_version_macro),
     },
     InjectableMacro {
         name: "is_http3_version",
-        handler: Handler::NoAttrPosition(is_http3_version_macro),
+        handler: Handler::WithAttrPosition(is_http3_version_macro),
     },
     InjectableMacro {
         name: "is_http1_1_or_higher_version",
-        handler: Handler::NoAttrPosition(is_http1_1_or_higher_version_macro),
+        handler: Handler::WithAttrPosition(is_http1_1_or_higher_version_macro),
     },
     InjectableMacro {
         name: "is_http_version",
-        handler: Handler::NoAttrPosition(is_http_version_macro),
+        handler: Handler::WithAttrPosition(is_http_version_macro),
     },
     InjectableMacro {
         name: "is_unknown_version",
-        handler: Handler::NoAttrPosition(is_unknown_version_macro),
+        handler: Handler::WithAttrPosition(is_unknown_version_macro),
     },
 ];
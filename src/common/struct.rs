@@ -19,3 +19,13 @@ pub(crate) struct InjectableMacro {
     /// The handler for the macro.
     pub(crate) handler: Handler,
 }
+
+/// Represents the response a guard sends when its check fails.
+///
+/// Parsed from a `respond = ...` or `else = ...` option on guard macros.
+pub(crate) struct GuardResponse {
+    /// The status code to respond with.
+    pub(crate) status_code: Expr,
+    /// The optional body to respond with.
+    pub(crate) body: Option<Expr>,
+}
@@ -0,0 +1,45 @@
+use super::*;
+
+/// Implementation of Parse trait for ConditionData.
+///
+/// Parses a condition expression optionally followed by a `respond = ...` or `else = ...` option.
+/// A condition prefixed with `async`, or written as an `async` block, is awaited.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<ConditionData>` - Parsed ConditionData or error.
+impl Parse for ConditionData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let condition: Expr = if input.peek(Token![async])
+            && !input.peek2(token::Brace)
+            && !input.peek2(Token![move])
+        {
+            input.parse::<Token![async]>()?;
+            let condition: Expr = input.parse()?;
+            parse_quote!((#condition).await)
+        } else {
+            match input.parse()? {
+                Expr::Async(condition) => parse_quote!(#condition.await),
+                condition => condition,
+            }
+        };
+        let mut response: Option<GuardResponse> = None;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if !input.is_empty() {
+                response = Some(input.parse()?);
+                if !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                }
+            }
+        }
+        Ok(ConditionData {
+            condition,
+            response,
+        })
+    }
+}
@@ -1,5 +1,7 @@
 mod r#fn;
+mod r#impl;
+mod r#struct;
 
-pub(crate) use r#fn::*;
+pub(crate) use {r#fn::*, r#struct::*};
 
 use super::*;
@@ -0,0 +1,11 @@
+use super::*;
+
+/// Condition data container for the `filter` and `reject` guards.
+///
+/// Stores the boolean condition and the optional response sent when the guard stops the request.
+pub(crate) struct ConditionData {
+    /// The boolean condition expression.
+    pub(crate) condition: Expr,
+    /// The optional response sent when the guard stops the request.
+    pub(crate) response: Option<GuardResponse>,
+}
@@ -0,0 +1,11 @@
+use super::*;
+
+/// Defines what a typed WebSocket loop does with a frame that fails to decode.
+pub(crate) enum DecodeErrorAction {
+    /// Ignores the frame and waits for the next one.
+    Skip,
+    /// Sends a close frame and ends the loop.
+    Close,
+    /// Sends the expression as a frame and waits for the next one.
+    Respond(Expr),
+}
@@ -3,7 +3,8 @@ use super::*;
 /// Implementation of Parse trait for FromStreamData.
 ///
 /// This implementation handles parsing of macro attributes that specify stream processing parameters.
-/// It supports an optional variable name parameter.
+/// It supports an optional variable name parameter, an optional `: Type` for decoding WebSocket
+/// frames as JSON, and trailing `name = value` options.
 ///
 /// # Arguments
 /// - `input`: The parse stream containing the token stream to be parsed
@@ -15,13 +16,16 @@ use super::*;
 /// # Errors
 /// This function returns an error when:
 /// - More than one parameter is provided
+/// - An option is unknown or repeated, or `on_decode_error` lacks a typed variable
 impl Parse for FromStreamData {
     /// Parses the input token stream into a FromStreamData structure.
     ///
     /// This method implements the core parsing logic for the FromStream macro attribute.
-    /// It handles two possible parameter configurations:
+    /// It handles the following parameter configurations:
     /// 1. Single parameter: interpreted as variable name
-    /// 2. No parameters: variable_name will be None
+    /// 2. `variable: Type`: frames are decoded as JSON into `Type`
+    /// 3. `on_decode_error = skip | close | respond(expr)` after a typed variable
+    /// 4. No parameters: variable_name will be None
     ///
     /// # Arguments
     /// - `ParseStream`: The ParseStream containing the token stream to be parsed
@@ -34,19 +38,117 @@ impl Parse for FromStreamData {
     /// # Errors
     /// The function returns errors in the following cases:
     /// - More than one parameter is provided
+    /// - An option is unknown or repeated, or `on_decode_error` lacks a typed variable
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let variable_name: Option<Expr> = if input.is_empty() {
-            None
-        } else {
+        let mut data: FromStreamData = FromStreamData {
+            variable_name: None,
+            message_type: None,
+            on_decode_error: DecodeErrorAction::Skip,
+            ping_interval_millis: None,
+            max_requests: None,
+            idle_timeout_millis: None,
+            on_close: None,
+            on_error: None,
+        };
+        if input.is_empty() {
+            return Ok(data);
+        }
+        if !peek_stream_option(input) {
             let expr: Expr = input.parse()?;
-            if !input.is_empty() {
+            if input.peek(Token![:]) && !input.peek(Token![::]) {
+                input.parse::<Token![:]>()?;
+                data.message_type = Some(input.parse()?);
+            }
+            data.variable_name = Some(expr);
+            if input.is_empty() {
+                return Ok(data);
+            }
+            input.parse::<Token![,]>()?;
+        }
+        let mut has_decode_error_option: bool = false;
+        while !input.is_empty() {
+            if !peek_stream_option(input) {
                 return Err(syn::Error::new(
                     input.span(),
                     "expected at most one parameter",
                 ));
             }
-            Some(expr)
-        };
-        Ok(FromStreamData { variable_name })
+            let name: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            if name == STREAM_ON_DECODE_ERROR_OPTION {
+                if data.message_type.is_none() || has_decode_error_option {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        "`on_decode_error` requires a typed variable such as `msg: Message` and may be given once",
+                    ));
+                }
+                has_decode_error_option = true;
+                let action: Ident = input.parse()?;
+                data.on_decode_error = if action == DECODE_ERROR_SKIP {
+                    DecodeErrorAction::Skip
+                } else if action == DECODE_ERROR_CLOSE {
+                    DecodeErrorAction::Close
+                } else if action == DECODE_ERROR_RESPOND && input.peek(token::Paren) {
+                    let content;
+                    parenthesized!(content in input);
+                    DecodeErrorAction::Respond(content.parse()?)
+                } else {
+                    return Err(syn::Error::new(
+                        action.span(),
+                        "expected `skip`, `close` or `respond(frame)`",
+                    ));
+                };
+            } else if name == STREAM_PING_INTERVAL_OPTION && data.ping_interval_millis.is_none() {
+                data.ping_interval_millis = Some(parse_duration_millis(&input.parse()?)?);
+            } else if name == STREAM_MAX_REQUESTS_OPTION && data.max_requests.is_none() {
+                let max_requests: LitInt = input.parse()?;
+                let value: usize = max_requests.base10_parse()?;
+                if value == 0 {
+                    return Err(syn::Error::new_spanned(
+                        max_requests,
+                        "expected a positive number of requests",
+                    ));
+                }
+                data.max_requests = Some(value);
+            } else if name == STREAM_IDLE_TIMEOUT_OPTION && data.idle_timeout_millis.is_none() {
+                data.idle_timeout_millis = Some(parse_duration_millis(&input.parse()?)?);
+            } else if name == STREAM_ON_CLOSE_OPTION && data.on_close.is_none() {
+                data.on_close = Some(input.parse()?);
+            } else if name == STREAM_ON_ERROR_OPTION && data.on_error.is_none() {
+                let closure: ExprClosure = input.parse()?;
+                if closure.inputs.len() != 1 {
+                    return Err(syn::Error::new_spanned(
+                        &closure.inputs,
+                        "expected `on_error = |error| { ... }` with exactly one parameter",
+                    ));
+                }
+                let pattern: Pat = closure.inputs.into_iter().next().unwrap();
+                data.on_error = Some((pattern, *closure.body));
+            } else {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("unknown or repeated option `{name}`"),
+                ));
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        Ok(data)
     }
 }
+
+/// Checks whether the input starts with a `name = value` stream option.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `bool` - `true` when the next tokens are an identifier followed by a single `=`.
+fn peek_stream_option(input: ParseStream) -> bool {
+    let fork: ParseStream = &input.fork();
+    fork.parse::<Ident>().is_ok() && fork.peek(Token![=]) && !fork.peek(Token![==])
+}
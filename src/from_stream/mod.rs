@@ -1,6 +1,7 @@
+mod r#enum;
 mod r#impl;
 mod r#struct;
 
-pub(crate) use r#struct::*;
+pub(crate) use {r#enum::*, r#struct::*};
 
 use super::*;
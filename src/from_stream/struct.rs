@@ -6,4 +6,18 @@ use super::*;
 pub(crate) struct FromStreamData {
     /// The variable name to store the read data.
     pub(crate) variable_name: Option<Expr>,
+    /// The optional type each WebSocket frame is decoded into as JSON.
+    pub(crate) message_type: Option<Type>,
+    /// What to do with a frame that fails to decode into the message type.
+    pub(crate) on_decode_error: DecodeErrorAction,
+    /// The optional interval between WebSocket pings, in milliseconds.
+    pub(crate) ping_interval_millis: Option<u64>,
+    /// The optional number of requests after which the HTTP loop closes the connection.
+    pub(crate) max_requests: Option<usize>,
+    /// The optional time without incoming data after which the loop ends, in milliseconds.
+    pub(crate) idle_timeout_millis: Option<u64>,
+    /// The optional cleanup block or async function run once when the loop ends.
+    pub(crate) on_close: Option<Expr>,
+    /// The optional `|error| body` run when reading fails, with the error bound to the pattern.
+    pub(crate) on_error: Option<(Pat, Expr)>,
 }
@@ -0,0 +1,23 @@
+use super::*;
+
+/// A boolean expression composed of guard checks.
+pub(crate) enum GuardExpr {
+    /// True when at least one of the nested expressions is true.
+    Any(Vec<GuardExpr>),
+    /// True when all of the nested expressions are true.
+    All(Vec<GuardExpr>),
+    /// True when the nested expression is false.
+    Not(Box<GuardExpr>),
+    /// An `is_*_method` check, holding the method name.
+    Method(Ident),
+    /// An `is_*_version` check, holding the version name.
+    Version(Ident),
+    /// An `is_*_upgrade_type` check, holding the upgrade type name.
+    Upgrade(Ident),
+    /// A `host(...)` check, true when the host matches one of the values.
+    Host(Vec<Expr>),
+    /// A `referer(...)` check, true when the Referer header matches one of the patterns.
+    Referer(Vec<RefererPattern>),
+    /// An `origin(...)` check, true when the Origin header matches one of the origin patterns.
+    Origin(Vec<Expr>),
+}
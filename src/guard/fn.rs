@@ -0,0 +1,62 @@
+use super::*;
+
+/// Generates the boolean expression of a composed guard.
+///
+/// Nested checks reuse the conditions of the corresponding guard macros and are
+/// combined with short-circuiting operators, so the whole guard is evaluated once.
+///
+/// # Arguments
+///
+/// - `&Ident` - The context identifier.
+/// - `&GuardExpr` - The guard expression.
+///
+/// # Returns
+///
+/// - `TokenStream2` - An expression that is `true` when the guard holds.
+pub(crate) fn guard_condition(context: &Ident, guard: &GuardExpr) -> proc_macro2::TokenStream {
+    match guard {
+        GuardExpr::Any(nested) => {
+            let conditions = nested.iter().map(|guard| guard_condition(context, guard));
+            quote! { (#(#conditions)||*) }
+        }
+        GuardExpr::All(nested) => {
+            let conditions = nested.iter().map(|guard| guard_condition(context, guard));
+            quote! { (#(#conditions)&&*) }
+        }
+        GuardExpr::Not(nested) => {
+            let condition: proc_macro2::TokenStream = guard_condition(context, nested);
+            quote! { !(#condition) }
+        }
+        GuardExpr::Method(method) => method_condition(context, method),
+        GuardExpr::Version(version) => version_condition(context, version),
+        GuardExpr::Upgrade(upgrade_type) => protocol_condition(context, upgrade_type),
+        GuardExpr::Host(values) => host_condition(context, values),
+        GuardExpr::Referer(values) => referer_condition(context, values),
+        GuardExpr::Origin(values) => origin_condition(context, values, &MissingHeader::Deny),
+    }
+}
+
+/// Filters requests with a guard composed of `any`, `all` and `not`.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with the composed guard.
+pub(crate) fn guard_macro(attr: TokenStream, item: TokenStream, position: Position) -> TokenStream {
+    let guard_data: GuardData = parse_macro_input!(attr as GuardData);
+    inject(position, item, |context: &Ident, stream: &Ident| {
+        let early_return: proc_macro2::TokenStream =
+            guard_return(context, stream, guard_data.response.as_ref());
+        let condition: proc_macro2::TokenStream = guard_condition(context, &guard_data.condition);
+        quote! {
+            if !#condition {
+                #early_return
+            }
+        }
+    })
+}
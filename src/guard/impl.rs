@@ -0,0 +1,154 @@
+use super::*;
+
+/// Implementation of Parse trait for GuardExpr.
+///
+/// Parses `any(...)`, `all(...)`, `not(...)`, `host(...)`, `referer(...)`, `origin(...)` and the
+/// `is_*_method`, `is_*_version` and `is_*_upgrade_type` checks.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<GuardExpr>` - Parsed GuardExpr or error.
+impl Parse for GuardExpr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        let name_str: String = name.to_string();
+        match name_str.as_str() {
+            "any" | "all" | "not" => {
+                let content;
+                parenthesized!(content in input);
+                let nested: Vec<GuardExpr> =
+                    Punctuated::<GuardExpr, Token![,]>::parse_terminated(&content)?
+                        .into_iter()
+                        .collect();
+                if nested.is_empty() {
+                    return Err(syn::Error::new_spanned(
+                        &name,
+                        format!("`{name_str}` expects at least one condition"),
+                    ));
+                }
+                match name_str.as_str() {
+                    "any" => Ok(GuardExpr::Any(nested)),
+                    "all" => Ok(GuardExpr::All(nested)),
+                    _ if nested.len() == 1 => {
+                        Ok(GuardExpr::Not(Box::new(nested.into_iter().next().unwrap())))
+                    }
+                    _ => Err(syn::Error::new_spanned(
+                        &name,
+                        "`not` expects exactly one condition",
+                    )),
+                }
+            }
+            "host" | "origin" => {
+                let content;
+                parenthesized!(content in input);
+                let values: Vec<Expr> = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?
+                    .into_iter()
+                    .collect();
+                if values.is_empty() {
+                    return Err(syn::Error::new_spanned(
+                        &name,
+                        format!("`{name_str}` expects at least one value"),
+                    ));
+                }
+                if name_str == "host" {
+                    values.iter().try_for_each(validate_host_pattern)?;
+                    Ok(GuardExpr::Host(values))
+                } else {
+                    values
+                        .iter()
+                        .try_for_each(|value: &Expr| validate_origin_pattern(value, false))?;
+                    Ok(GuardExpr::Origin(values))
+                }
+            }
+            "referer" => {
+                let content;
+                parenthesized!(content in input);
+                let values: Vec<RefererPattern> =
+                    Punctuated::<RefererPattern, Token![,]>::parse_terminated(&content)?
+                        .into_iter()
+                        .collect();
+                if values.is_empty() {
+                    return Err(syn::Error::new_spanned(
+                        &name,
+                        "`referer` expects at least one value",
+                    ));
+                }
+                Ok(GuardExpr::Referer(values))
+            }
+            _ => {
+                let check = |suffix: &str, known: &[&str]| -> Option<Ident> {
+                    name_str
+                        .strip_prefix("is_")
+                        .and_then(|rest: &str| rest.strip_suffix(suffix))
+                        .filter(|check: &&str| known.contains(check))
+                        .map(|check: &str| Ident::new(check, name.span()))
+                };
+                if let Some(method) = check("_method", &GUARD_METHODS) {
+                    Ok(GuardExpr::Method(method))
+                } else if let Some(version) = check("_version", &GUARD_VERSIONS) {
+                    Ok(GuardExpr::Version(version))
+                } else if let Some(upgrade_type) = check("_upgrade_type", &GUARD_UPGRADE_TYPES) {
+                    Ok(GuardExpr::Upgrade(upgrade_type))
+                } else {
+                    Err(syn::Error::new_spanned(
+                        &name,
+                        format!(
+                            "unsupported guard `{name_str}`, expected `any`, `all`, `not`, `host`, `referer`, `origin` or an `is_*_method`, `is_*_version` or `is_*_upgrade_type` check"
+                        ),
+                    ))
+                }
+            }
+        }
+    }
+}
+
+/// Implementation of Parse trait for GuardData.
+///
+/// Parses one or more guard expressions, all of which must hold, optionally followed by
+/// a `respond = ...` or `else = ...` option.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<GuardData>` - Parsed GuardData or error.
+impl Parse for GuardData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut conditions: Vec<GuardExpr> = Vec::new();
+        let mut response: Option<GuardResponse> = None;
+        loop {
+            if peek_guard_response(input) {
+                if conditions.is_empty() || response.is_some() {
+                    return Err(input.error(
+                        "expected at least one guard condition and at most one `respond` or `else` option",
+                    ));
+                }
+                response = Some(input.parse()?);
+            } else {
+                conditions.push(input.parse()?);
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+        }
+        let condition: GuardExpr = if conditions.len() == 1 {
+            conditions.remove(0)
+        } else {
+            GuardExpr::All(conditions)
+        };
+        Ok(GuardData {
+            condition,
+            response,
+        })
+    }
+}
@@ -0,0 +1,11 @@
+use super::*;
+
+/// Guard data container for the `guard` macro.
+///
+/// Stores the composed condition and the optional response sent when the guard stops the request.
+pub(crate) struct GuardData {
+    /// The composed guard expression.
+    pub(crate) condition: GuardExpr,
+    /// The optional response sent when the guard stops the request.
+    pub(crate) response: Option<GuardResponse>,
+}
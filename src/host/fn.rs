@@ -1,7 +1,106 @@
 use super::*;
 
-/// Filters requests matching the specified host.
-/// Supports both single and multiple host value checks.
+/// Validates a literal host pattern.
+///
+/// A `*` is only allowed on its own or as the leading `*.` label of a pattern.
+///
+/// # Arguments
+///
+/// - `&Expr` - The host value expression.
+///
+/// # Returns
+///
+/// - `syn::Result<()>` - An error spanned at the pattern when a literal pattern is malformed.
+pub(crate) fn validate_host_pattern(host_value: &Expr) -> syn::Result<()> {
+    if let Expr::Lit(ExprLit {
+        lit: Lit::Str(pattern),
+        ..
+    }) = host_value
+    {
+        let pattern: String = pattern.value();
+        let wildcard_free: &str = if pattern == "*" {
+            ""
+        } else {
+            pattern.strip_prefix("*.").unwrap_or(&pattern)
+        };
+        if wildcard_free.contains('*') {
+            return Err(syn::Error::new_spanned(
+                host_value,
+                "host patterns support `*` only on its own or as a leading `*.` label",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Generates the expression matching the request host against several values.
+///
+/// Hosts and values are compared case-insensitively with any port removed. A value of
+/// `*.example.com` matches every subdomain of `example.com` but not `example.com` itself,
+/// and a value of `*` matches every host.
+///
+/// # Arguments
+///
+/// - `&Ident` - The context identifier.
+/// - `&[Expr]` - The host value expressions.
+///
+/// # Returns
+///
+/// - `TokenStream2` - An `Option<String>` expression holding the part matched by the wildcard
+///   of the first matching value, or an empty string for an exact match.
+pub(crate) fn host_match(context: &Ident, host_values: &[Expr]) -> proc_macro2::TokenStream {
+    quote! {
+        {
+            fn strip_port(host: &str) -> &str {
+                if host.starts_with('[') {
+                    return host.find(']').map_or(host, |end: usize| &host[..=end]);
+                }
+                match host.split_once(':') {
+                    Some((name, port)) if !port.contains(':') => name,
+                    _ => host,
+                }
+            }
+            fn match_host(host: &str, pattern: &str) -> ::std::option::Option<::std::string::String> {
+                let host: ::std::string::String = strip_port(host).to_ascii_lowercase();
+                let pattern: ::std::string::String = strip_port(pattern).to_ascii_lowercase();
+                if pattern == "*" {
+                    return Some(host);
+                }
+                match pattern.strip_prefix("*.") {
+                    Some(suffix) => host
+                        .strip_suffix(suffix)
+                        .and_then(|subdomain: &str| subdomain.strip_suffix('.'))
+                        .filter(|subdomain: &&str| !subdomain.is_empty())
+                        .map(|subdomain: &str| subdomain.to_owned()),
+                    None => (host == pattern).then(::std::string::String::new),
+                }
+            }
+            let host: &str = #context.get_request().get_host();
+            ::std::option::Option::<::std::string::String>::None
+                #(.or_else(|| match_host(host, ::std::convert::AsRef::<str>::as_ref(&#host_values))))*
+        }
+    }
+}
+
+/// Generates the boolean expression checking the request host against several values.
+///
+/// # Arguments
+///
+/// - `&Ident` - The context identifier.
+/// - `&[Expr]` - The host value expressions.
+///
+/// # Returns
+///
+/// - `TokenStream2` - An expression that is `true` when the host matches any of the values.
+pub(crate) fn host_condition(context: &Ident, host_values: &[Expr]) -> proc_macro2::TokenStream {
+    let host_match: proc_macro2::TokenStream = host_match(context, host_values);
+    quote! {
+        #host_match.is_some()
+    }
+}
+
+/// Filters requests matching one of the specified hosts.
+/// Supports exact values and wildcard patterns, and can capture the matched subdomain.
 ///
 /// # Arguments
 ///
@@ -14,22 +113,24 @@ use super::*;
 /// - `TokenStream` - The expanded token stream with host filter.
 pub(crate) fn host_macro(attr: TokenStream, item: TokenStream, position: Position) -> TokenStream {
     let multi_host: MultiHostData = parse_macro_input!(attr as MultiHostData);
-    inject(position, item, |context: &Ident, _: &Ident| {
-        let statements = multi_host.host_values.iter().map(|host_value| {
-            quote! {
-                if #context.get_request().get_host() != #host_value {
-                    return ::hyperlane::Status::Continue;
-                }
-            }
-        });
+    inject(position, item, |context: &Ident, stream: &Ident| {
+        let early_return: proc_macro2::TokenStream =
+            guard_return(context, stream, multi_host.response.as_ref());
+        let host_match: proc_macro2::TokenStream = host_match(context, &multi_host.host_values);
+        let capture: proc_macro2::TokenStream = match &multi_host.capture {
+            Some(variable) => quote! { #variable },
+            None => quote! { _ },
+        };
         quote! {
-            #(#statements)*
+            let ::std::option::Option::Some(#capture) = (#host_match) else {
+                #early_return
+            };
         }
     })
 }
 
-/// Rejects requests matching the specified host.
-/// Supports both single and multiple host value checks.
+/// Rejects requests matching one of the specified hosts.
+/// Supports exact values and wildcard patterns.
 ///
 /// # Arguments
 ///
@@ -46,16 +147,19 @@ pub(crate) fn reject_host_macro(
     position: Position,
 ) -> TokenStream {
     let multi_host: MultiHostData = parse_macro_input!(attr as MultiHostData);
-    inject(position, item, |context: &Ident, _: &Ident| {
-        let statements = multi_host.host_values.iter().map(|host_value| {
-            quote! {
-                if #context.get_request().get_host() == #host_value {
-                    return ::hyperlane::Status::Continue;
-                }
-            }
-        });
+    if let Some(capture) = &multi_host.capture {
+        return syn::Error::new_spanned(capture, "`reject_host` cannot capture a subdomain")
+            .to_compile_error()
+            .into();
+    }
+    inject(position, item, |context: &Ident, stream: &Ident| {
+        let early_return: proc_macro2::TokenStream =
+            guard_return(context, stream, multi_host.response.as_ref());
+        let condition: proc_macro2::TokenStream = host_condition(context, &multi_host.host_values);
         quote! {
-            #(#statements)*
+            if #condition {
+                #early_return
+            }
         }
     })
 }
@@ -3,7 +3,8 @@ use super::*;
 /// Implementation of Parse trait for MultiHostData.
 ///
 /// Parses host value expressions from input stream.
-/// Supports both single and multiple host values.
+/// Supports both single and multiple host values, optionally followed by
+/// a `capture = variable` option and a `respond = ...` or `else = ...` option.
 ///
 /// # Arguments
 ///
@@ -15,9 +16,36 @@ use super::*;
 impl Parse for MultiHostData {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let mut host_values: Vec<Expr> = Vec::new();
+        let mut capture: Option<Ident> = None;
+        let mut response: Option<GuardResponse> = None;
         loop {
-            let host_value: Expr = input.parse()?;
-            host_values.push(host_value);
+            let fork: ParseStream = &input.fork();
+            let is_capture: bool = fork
+                .parse::<Ident>()
+                .is_ok_and(|name: Ident| name == CAPTURE_OPTION)
+                && fork.peek(Token![=])
+                && !fork.peek(Token![==]);
+            if is_capture {
+                if host_values.is_empty() || capture.is_some() {
+                    return Err(input.error(
+                        "expected at least one host value and at most one `capture` option",
+                    ));
+                }
+                input.parse::<Ident>()?;
+                input.parse::<Token![=]>()?;
+                capture = Some(input.parse()?);
+            } else if peek_guard_response(input) {
+                if host_values.is_empty() || response.is_some() {
+                    return Err(input.error(
+                        "expected at least one host value and at most one `respond` or `else` option",
+                    ));
+                }
+                response = Some(input.parse()?);
+            } else {
+                let host_value: Expr = input.parse()?;
+                validate_host_pattern(&host_value)?;
+                host_values.push(host_value);
+            }
             if input.is_empty() {
                 break;
             }
@@ -26,6 +54,10 @@ impl Parse for MultiHostData {
                 break;
             }
         }
-        Ok(MultiHostData { host_values })
+        Ok(MultiHostData {
+            host_values,
+            capture,
+            response,
+        })
     }
 }
@@ -7,4 +7,8 @@ use super::*;
 pub(crate) struct MultiHostData {
     /// Vector of host value expressions to match against.
     pub(crate) host_values: Vec<Expr>,
+    /// The optional variable bound to the subdomain matched by a wildcard.
+    pub(crate) capture: Option<Ident>,
+    /// The optional response sent when the guard stops the request.
+    pub(crate) response: Option<GuardResponse>,
 }
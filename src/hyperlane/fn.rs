@@ -4,7 +4,8 @@ use super::*;
 /// Supports both single and multiple variable-type pair initialization.
 ///
 /// This macro expects an attribute in the format `#[hyperlane(variable_name: TypeName)]`
-/// or `#[hyperlane(var1: Type1, var2: Type2, ...)]`.
+/// or `#[hyperlane(var1: Type1, var2: Type2, ...)]`. A `cookie_key = KEY` entry builds the
+/// declared cookie key before the function body runs, so an invalid key fails at startup.
 ///
 /// # Arguments
 ///
@@ -38,6 +39,11 @@ pub(crate) fn hyperlane_macro(attr: TokenStream, item: TokenStream) -> TokenStre
             });
         }
     }
+    for cookie_key in &multi_hyperlane.cookie_keys {
+        init_statements.push(quote! {
+            ::std::sync::LazyLock::force(&#cookie_key);
+        });
+    }
     let gen_code: proc_macro2::TokenStream = quote! {
         #(#attrs)*
         #vis #sig {
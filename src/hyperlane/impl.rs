@@ -5,6 +5,8 @@ use super::*;
 /// This implementation allows parsing multiple variable-type pairs from a token stream,
 /// expecting the format `variable_name: TypeName, variable_name2: TypeName2, ...`.
 /// Also supports single pair format for backward compatibility.
+/// A `cookie_key = KEY` entry builds the key declared with `signed_cookie_key!` or
+/// `encrypted_cookie_key!` at startup, and may be repeated.
 ///
 /// # Arguments
 ///
@@ -16,11 +18,20 @@ use super::*;
 impl Parse for MultiHyperlaneAttr {
     fn parse(input: ParseStream) -> Result<Self> {
         let mut params: Vec<(Ident, Ident)> = Vec::new();
+        let mut cookie_keys: Vec<Path> = Vec::new();
         loop {
             let var_name: Ident = input.parse()?;
-            input.parse::<Token![:]>()?;
-            let type_name: Ident = input.parse()?;
-            params.push((var_name, type_name));
+            if input.peek(Token![=]) {
+                if var_name != COOKIE_KEY_OPTION {
+                    return Err(syn::Error::new_spanned(&var_name, "expected `cookie_key`"));
+                }
+                input.parse::<Token![=]>()?;
+                cookie_keys.push(input.parse()?);
+            } else {
+                input.parse::<Token![:]>()?;
+                let type_name: Ident = input.parse()?;
+                params.push((var_name, type_name));
+            }
             if input.is_empty() {
                 break;
             }
@@ -29,6 +40,9 @@ impl Parse for MultiHyperlaneAttr {
                 break;
             }
         }
-        Ok(MultiHyperlaneAttr { params })
+        Ok(MultiHyperlaneAttr {
+            params,
+            cookie_keys,
+        })
     }
 }
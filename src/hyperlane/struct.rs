@@ -7,4 +7,6 @@ use super::*;
 pub(crate) struct MultiHyperlaneAttr {
     /// Vector of variable-type pairs.
     pub(crate) params: Vec<(Ident, Ident)>,
+    /// The cookie keys built at startup for the signed cookie macros.
+    pub(crate) cookie_keys: Vec<Path>,
 }
@@ -0,0 +1,251 @@
+use super::*;
+
+/// Parses a literal IP range in CIDR notation.
+///
+/// A plain address is treated as a range containing only that address.
+///
+/// # Arguments
+///
+/// - `&LitStr` - The range literal, such as `"10.0.0.0/8"` or `"::1"`.
+///
+/// # Returns
+///
+/// - `syn::Result<(IpAddr, u8)>` - The network address and prefix length, or an error spanned at the literal.
+pub(crate) fn parse_ip_range(range: &LitStr) -> syn::Result<(IpAddr, u8)> {
+    let value: String = range.value();
+    let (address, prefix) = match value.split_once('/') {
+        Some((address, prefix)) => (address, Some(prefix)),
+        None => (value.as_str(), None),
+    };
+    let address: IpAddr = address.parse().map_err(|_| {
+        syn::Error::new_spanned(range, format!("`{address}` is not a valid IP address"))
+    })?;
+    let max_prefix: u8 = if address.is_ipv4() { 32 } else { 128 };
+    let prefix: u8 = match prefix {
+        Some(prefix) => prefix
+            .parse::<u8>()
+            .ok()
+            .filter(|prefix: &u8| *prefix <= max_prefix)
+            .ok_or_else(|| {
+                syn::Error::new_spanned(
+                    range,
+                    format!("`{prefix}` is not a valid prefix length, expected 0 to {max_prefix}"),
+                )
+            })?,
+        None => max_prefix,
+    };
+    Ok((address, prefix))
+}
+
+/// Generates the expression constructing a parsed IP range.
+///
+/// # Arguments
+///
+/// - `&(IpAddr, u8)` - The network address and prefix length.
+///
+/// # Returns
+///
+/// - `TokenStream2` - An `(IpAddr, u8)` tuple expression.
+fn ip_range_tokens((address, prefix): &(IpAddr, u8)) -> proc_macro2::TokenStream {
+    let address: proc_macro2::TokenStream = match address {
+        IpAddr::V4(address) => {
+            let octets: [u8; 4] = address.octets();
+            quote! { ::std::net::IpAddr::from([#(#octets),*]) }
+        }
+        IpAddr::V6(address) => {
+            let octets: [u8; 16] = address.octets();
+            quote! { ::std::net::IpAddr::from([#(#octets),*]) }
+        }
+    };
+    quote! { (#address, #prefix) }
+}
+
+/// Generates statements determining the client address of a request.
+///
+/// The statements define an `in_ranges` helper and bind `client_ip` to the peer address of
+/// the connection. When the peer is one of the trusted proxies, `client_ip` is the rightmost
+/// `X-Forwarded-For` entry that is not a trusted proxy, falling back to `X-Real-IP` and then
+/// to the peer itself.
+///
+/// # Arguments
+///
+/// - `&Ident` - The context identifier.
+/// - `&Ident` - The stream identifier.
+/// - `&[(IpAddr, u8)]` - The trusted proxy ranges.
+///
+/// # Returns
+///
+/// - `TokenStream2` - Statements binding `client_ip` to an `Option<IpAddr>`, `None` when the
+///   client address is unknown.
+pub(crate) fn client_ip_statements(
+    context: &Ident,
+    stream: &Ident,
+    trusted_proxies: &[(IpAddr, u8)],
+) -> proc_macro2::TokenStream {
+    let trusted_proxies = trusted_proxies.iter().map(ip_range_tokens);
+    quote! {
+        fn in_ranges(address: ::std::net::IpAddr, ranges: &[(::std::net::IpAddr, u8)]) -> bool {
+            let address: ::std::net::IpAddr = address.to_canonical();
+            ranges.iter().any(|(network, prefix)| {
+                let (network, prefix): (::std::net::IpAddr, u8) = match network {
+                    ::std::net::IpAddr::V6(network) => match network.to_ipv4_mapped() {
+                        Some(network) => (::std::net::IpAddr::V4(network), prefix.saturating_sub(96)),
+                        None => (::std::net::IpAddr::V6(*network), *prefix),
+                    },
+                    network => (*network, *prefix),
+                };
+                match (address, network) {
+                    (::std::net::IpAddr::V4(address), ::std::net::IpAddr::V4(network)) => {
+                        let mask: u32 = u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0);
+                        u32::from(address) & mask == u32::from(network) & mask
+                    }
+                    (::std::net::IpAddr::V6(address), ::std::net::IpAddr::V6(network)) => {
+                        let mask: u128 = u128::MAX.checked_shl(128 - u32::from(prefix)).unwrap_or(0);
+                        u128::from(address) & mask == u128::from(network) & mask
+                    }
+                    _ => false,
+                }
+            })
+        }
+        let trusted_proxies: &[(::std::net::IpAddr, u8)] = &[#(#trusted_proxies),*];
+        let peer: ::std::option::Option<::std::net::IpAddr> = #stream
+            .get_stream()
+            .peer_addr()
+            .ok()
+            .map(|address: ::std::net::SocketAddr| address.ip().to_canonical());
+        let client_ip: ::std::option::Option<::std::net::IpAddr> = match peer {
+            Some(peer) if in_ranges(peer, trusted_proxies) => {
+                let request: &::hyperlane::Request = #context.get_request();
+                request
+                    .try_get_header_back(::hyperlane::X_FORWARDED_FOR)
+                    .and_then(|forwarded_for| {
+                        forwarded_for
+                            .rsplit(',')
+                            .map(|address: &str| address.trim().parse::<::std::net::IpAddr>())
+                            .take_while(::std::result::Result::is_ok)
+                            .flatten()
+                            .find(|address: &::std::net::IpAddr| !in_ranges(*address, trusted_proxies))
+                    })
+                    .or_else(|| {
+                        request
+                            .try_get_header_back(::hyperlane::X_REAL_IP)
+                            .and_then(|real_ip| real_ip.trim().parse().ok())
+                    })
+                    .or(Some(peer))
+            }
+            peer => peer,
+        };
+    }
+}
+
+/// Generates the expression checking whether the client address lies in the configured ranges.
+///
+/// Entries of a `ranges = ...` expression are parsed on every request, and those that are
+/// not valid ranges are skipped.
+///
+/// # Arguments
+///
+/// - `&Ident` - The context identifier.
+/// - `&Ident` - The stream identifier.
+/// - `&IpRangeData` - The parsed guard data.
+///
+/// # Returns
+///
+/// - `TokenStream2` - An `Option<bool>` expression, `None` when the client address is unknown.
+fn ip_range_match(context: &Ident, stream: &Ident, data: &IpRangeData) -> proc_macro2::TokenStream {
+    let ranges = data.ranges.iter().map(ip_range_tokens);
+    let mutability: Option<Token![mut]> = data.dynamic_ranges.as_ref().map(|_| parse_quote!(mut));
+    let dynamic_ranges: proc_macro2::TokenStream = match &data.dynamic_ranges {
+        Some(dynamic_ranges) => quote! {
+            fn parse_range(range: &str) -> ::std::option::Option<(::std::net::IpAddr, u8)> {
+                let (address, prefix) = match range.trim().split_once('/') {
+                    Some((address, prefix)) => (address, Some(prefix)),
+                    None => (range.trim(), None),
+                };
+                let address: ::std::net::IpAddr = address.parse().ok()?;
+                let max_prefix: u8 = if address.is_ipv4() { 32 } else { 128 };
+                let prefix: u8 = match prefix {
+                    Some(prefix) => prefix.parse().ok().filter(|prefix: &u8| *prefix <= max_prefix)?,
+                    None => max_prefix,
+                };
+                Some((address, prefix))
+            }
+            for range in #dynamic_ranges {
+                if let Some(range) = parse_range(::std::convert::AsRef::<str>::as_ref(&range)) {
+                    ranges.push(range);
+                }
+            }
+        },
+        None => quote! {},
+    };
+    let client_ip: proc_macro2::TokenStream =
+        client_ip_statements(context, stream, &data.trusted_proxies);
+    quote! {
+        {
+            let #mutability ranges: ::std::vec::Vec<(::std::net::IpAddr, u8)> = ::std::vec![#(#ranges),*];
+            #dynamic_ranges
+            #client_ip
+            client_ip.map(|client_ip: ::std::net::IpAddr| in_ranges(client_ip, &ranges))
+        }
+    }
+}
+
+/// Filters requests whose client address lies in one of the specified IP ranges.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with the IP allowlist check.
+pub(crate) fn allow_ip_macro(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+) -> TokenStream {
+    let ip_range: IpRangeData = parse_macro_input!(attr as IpRangeData);
+    inject(position, item, |context: &Ident, stream: &Ident| {
+        let early_return: proc_macro2::TokenStream =
+            guard_return(context, stream, ip_range.response.as_ref());
+        let range_match: proc_macro2::TokenStream = ip_range_match(context, stream, &ip_range);
+        quote! {
+            if #range_match != Some(true) {
+                #early_return
+            }
+        }
+    })
+}
+
+/// Rejects requests whose client address lies in one of the specified IP ranges.
+///
+/// Requests whose client address cannot be determined are rejected as well.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with the IP denylist check.
+pub(crate) fn deny_ip_macro(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+) -> TokenStream {
+    let ip_range: IpRangeData = parse_macro_input!(attr as IpRangeData);
+    inject(position, item, |context: &Ident, stream: &Ident| {
+        let early_return: proc_macro2::TokenStream =
+            guard_return(context, stream, ip_range.response.as_ref());
+        let range_match: proc_macro2::TokenStream = ip_range_match(context, stream, &ip_range);
+        quote! {
+            if #range_match != Some(false) {
+                #early_return
+            }
+        }
+    })
+}
@@ -0,0 +1,76 @@
+use super::*;
+
+/// Implementation of Parse trait for IpRangeData.
+///
+/// Parses literal IP ranges, a `ranges = expr` option, a `trusted_proxies(...)` option
+/// and a `respond = ...` or `else = ...` option.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<IpRangeData>` - Parsed IpRangeData or error.
+impl Parse for IpRangeData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut data: IpRangeData = IpRangeData {
+            ranges: Vec::new(),
+            dynamic_ranges: None,
+            trusted_proxies: Vec::new(),
+            response: None,
+        };
+        let mut has_trusted_proxies: bool = false;
+        while !input.is_empty() {
+            if peek_guard_response(input) {
+                if data.response.is_some() {
+                    return Err(input.error("at most one `respond` or `else` option is allowed"));
+                }
+                data.response = Some(input.parse()?);
+            } else if input.peek(LitStr) {
+                data.ranges.push(parse_ip_range(&input.parse()?)?);
+            } else {
+                let name: Ident = input.parse()?;
+                if name == IP_RANGES_OPTION && input.peek(Token![=]) {
+                    if data.dynamic_ranges.is_some() {
+                        return Err(syn::Error::new_spanned(
+                            &name,
+                            "`ranges` is specified more than once",
+                        ));
+                    }
+                    input.parse::<Token![=]>()?;
+                    data.dynamic_ranges = Some(input.parse()?);
+                } else if name == IP_TRUSTED_PROXIES_OPTION && input.peek(token::Paren) {
+                    if has_trusted_proxies {
+                        return Err(syn::Error::new_spanned(
+                            &name,
+                            "`trusted_proxies` is specified more than once",
+                        ));
+                    }
+                    has_trusted_proxies = true;
+                    let content;
+                    parenthesized!(content in input);
+                    for proxy in Punctuated::<LitStr, Token![,]>::parse_terminated(&content)? {
+                        data.trusted_proxies.push(parse_ip_range(&proxy)?);
+                    }
+                } else {
+                    return Err(syn::Error::new_spanned(
+                        &name,
+                        "expected an IP range string literal, `ranges = ...`, `trusted_proxies(...)`, `respond = ...` or `else = ...`",
+                    ));
+                }
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        if data.ranges.is_empty() && data.dynamic_ranges.is_none() {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "expected at least one IP range or a `ranges = ...` option",
+            ));
+        }
+        Ok(data)
+    }
+}
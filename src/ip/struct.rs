@@ -0,0 +1,16 @@
+use super::*;
+
+/// IP range data container for the `allow_ip` and `deny_ip` guards.
+///
+/// Literal ranges are parsed when the macro expands. Ranges given by `ranges = ...`
+/// are parsed on every request.
+pub(crate) struct IpRangeData {
+    /// The literal ranges as network addresses and prefix lengths.
+    pub(crate) ranges: Vec<(IpAddr, u8)>,
+    /// The optional expression yielding further ranges at runtime.
+    pub(crate) dynamic_ranges: Option<Expr>,
+    /// The proxies trusted to report the client address in forwarding headers.
+    pub(crate) trusted_proxies: Vec<(IpAddr, u8)>,
+    /// The optional response sent when the guard stops the request.
+    pub(crate) response: Option<GuardResponse>,
+}
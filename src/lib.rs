@@ -11,37 +11,53 @@ mod context;
 mod filter;
 mod flush;
 mod from_stream;
+mod guard;
 mod hook;
 mod host;
 mod hyperlane;
 mod inject;
+mod ip;
+mod media_type;
 mod method;
+mod rate_limit;
+mod redirect;
 mod referer;
 mod reject;
 mod request;
 mod request_middleware;
+mod require;
+mod respond;
 mod response;
 mod response_middleware;
 mod route;
 mod send;
+mod signed_cookie;
+mod sse;
 mod stream;
 mod upgrade;
 mod version;
+mod websocket;
+mod ws_dispatch;
+mod ws_room;
 
 use {
-    closed::*, common::*, context::*, filter::*, flush::*, from_stream::*, hook::*, host::*,
-    hyperlane::*, inject::*, method::*, referer::*, reject::*, request::*, request_middleware::*,
-    response::*, response_middleware::*, route::*, send::*, stream::*, upgrade::*, version::*,
+    closed::*, common::*, context::*, filter::*, flush::*, from_stream::*, guard::*, hook::*,
+    host::*, hyperlane::*, inject::*, ip::*, media_type::*, method::*, rate_limit::*, redirect::*,
+    referer::*, reject::*, request::*, request_middleware::*, require::*, respond::*, response::*,
+    response_middleware::*, route::*, send::*, signed_cookie::*, sse::*, stream::*, upgrade::*,
+    version::*, websocket::*, ws_dispatch::*, ws_room::*,
 };
 
 use {
     proc_macro::TokenStream,
     proc_macro2::Span,
     quote::quote,
+    std::net::IpAddr,
     syn::{
         Ident, Token,
         parse::{Parse, ParseStream, Parser, Result},
         punctuated::Punctuated,
+        spanned::Spanned,
         token::Comma,
         *,
     },
@@ -117,6 +133,82 @@ use {
 /// #[try_get_websocket_request]
 /// async fn standalone_try_get_websocket_request_handler(stream: &mut Stream, ctx: &mut Context) -> Status {}
 /// ```
+///
+/// Decoding each frame as JSON into a typed message:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::try_get_websocket_request;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct ChatMessage {
+///     text: String,
+/// }
+///
+/// #[try_get_websocket_request(msg: ChatMessage)]
+/// async fn typed_websocket_handler(stream: &mut Stream, ctx: &mut Context) -> Status {
+///     let body_list: Vec<ResponseBody> = WebSocketFrame::create_frame_list(&msg.text);
+///     stream.send_list(body_list).await;
+/// }
+///
+/// #[try_get_websocket_request(msg: ChatMessage, on_decode_error = respond(format!("invalid message: {decode_error}")))]
+/// async fn responding_typed_websocket_handler(stream: &mut Stream, ctx: &mut Context) -> Status {}
+///
+/// #[try_get_websocket_request(msg: ChatMessage, on_decode_error = close)]
+/// async fn closing_typed_websocket_handler(stream: &mut Stream, ctx: &mut Context) -> Status {}
+/// ```
+///
+/// A frame that fails to decode never reaches the function body. With `on_decode_error = skip`,
+/// the default, it is ignored; `close` sends a close frame with code `1007` and ends the loop;
+/// `respond(frame)` sends `frame` back, with the `serde_json::Error` bound to `decode_error`.
+///
+/// Sending heartbeats, dropping idle peers and cleaning up when the connection ends:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::try_get_websocket_request;
+///
+/// async fn handle_close(stream: &mut Stream, ctx: &mut Context) {}
+///
+/// #[try_get_websocket_request(body, ping_interval = "30s", idle_timeout = "2m", on_close = handle_close)]
+/// async fn heartbeat_websocket_handler(stream: &mut Stream, ctx: &mut Context) -> Status {
+///     let body_list: Vec<ResponseBody> = WebSocketFrame::create_frame_list(&body);
+///     stream.send_list(body_list).await;
+/// }
+///
+/// #[try_get_websocket_request(idle_timeout = "90s", on_close = { ctx.get_mut_response().clear_headers(); })]
+/// async fn idle_websocket_handler(stream: &mut Stream, ctx: &mut Context) -> Status {}
+/// ```
+///
+/// `ping_interval` sends a ping frame at that interval while waiting for a message.
+/// `idle_timeout` sends a close frame and ends the loop once no frame, including pongs,
+/// arrived for that long. Messages are still read by `Stream::try_get_websocket_request`.
+/// `on_close` takes a block, or an async function called with the stream and the context,
+/// and runs once after the loop ends for any reason, including an early `return` from the
+/// function body.
+///
+/// Handling the error that ended the loop:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::try_get_websocket_request;
+///
+/// #[try_get_websocket_request(body, on_error = |error| {
+///     if !matches!(error, RequestError::ClientClosedConnection(_)) {
+///         eprintln!("websocket error: {error}");
+///         return Status::Reject;
+///     }
+/// })]
+/// async fn erroring_websocket_handler(stream: &mut Stream, ctx: &mut Context) -> Status {}
+/// ```
+///
+/// `on_error = |error| { ... }` runs after the loop with the `RequestError` that ended it bound
+/// to the pattern. Its body may await, send frames or `return` a `Status`; otherwise the
+/// function returns `Status::Continue`. A loop left through `break` does not run it.
 #[proc_macro_attribute]
 pub fn try_get_websocket_request(attr: TokenStream, item: TokenStream) -> TokenStream {
     try_get_websocket_request_macro(attr, item)
@@ -184,6 +276,52 @@ pub fn try_get_websocket_request(attr: TokenStream, item: TokenStream) -> TokenS
 /// #[try_get_http_request]
 /// async fn standalone_try_get_http_request_handler(stream: &mut Stream, ctx: &mut Context) -> Status {}
 /// ```
+///
+/// Ending idle connections and cleaning up afterwards:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::try_get_http_request;
+///
+/// async fn handle_close(stream: &mut Stream, ctx: &mut Context) {}
+///
+/// #[try_get_http_request(request, idle_timeout = "15s", on_close = handle_close)]
+/// async fn idle_http_handler(stream: &mut Stream, ctx: &mut Context) -> Status {}
+///
+/// #[try_get_http_request(request, max_requests = 1000, idle_timeout = "15s")]
+/// async fn limited_http_handler(stream: &mut Stream, ctx: &mut Context) -> Status {
+///     stream.send(ctx.get_mut_response().build()).await;
+/// }
+/// ```
+///
+/// `idle_timeout` ends the loop when the next request does not arrive in time, and `on_close`
+/// takes a block, or an async function called with the stream and the context, that runs
+/// once after the loop ends for any reason.
+///
+/// When `max_requests`, `idle_timeout` or `on_close` is given, the loop also follows keep-alive
+/// semantics: after a request with `Connection: close`, an HTTP/1.0 request without
+/// `Connection: keep-alive`, or the `max_requests`-th request, the response gets
+/// `Connection: close` before the function body runs, and the connection is closed once the
+/// body has finished. Without them, the loop reads requests until reading fails.
+///
+/// Handling the error that ended the loop:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::try_get_http_request;
+///
+/// #[try_get_http_request(request, idle_timeout = "15s", on_error = |error| {
+///     if matches!(error, RequestError::ReadTimeout(_)) {
+///         return Status::Reject;
+///     }
+/// })]
+/// async fn erroring_http_handler(stream: &mut Stream, ctx: &mut Context) -> Status {}
+/// ```
+///
+/// `on_error = |error| { ... }` runs after the loop with the `RequestError` that ended it bound
+/// to the pattern, and may `return` a `Status`.
 #[proc_macro_attribute]
 pub fn try_get_http_request(attr: TokenStream, item: TokenStream) -> TokenStream {
     try_get_http_request_macro(attr, item)
@@ -221,11 +359,12 @@ pub fn try_get_http_request(attr: TokenStream, item: TokenStream) -> TokenStream
 /// async fn standalone_get_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
+/// The macro optionally takes an `else = code` or `else = (code, body)` option that sends
+/// that response when the check fails, and should be applied directly to async functions
 /// that accept a `&mut Context` parameter.
 #[proc_macro_attribute]
-pub fn is_get_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    is_get_method_handler(item, Position::Prologue)
+pub fn is_get_method(attr: TokenStream, item: TokenStream) -> TokenStream {
+    is_get_method_handler(attr, item, Position::Prologue)
 }
 
 /// Restricts function execution to HTTP POST requests only.
@@ -260,11 +399,23 @@ pub fn is_get_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// async fn standalone_post_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
+/// Sending a response when the check fails:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::is_post_method;
+///
+/// #[is_post_method(else = 405)]
+/// async fn responding_is_post_method_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// The macro optionally takes an `else = code` or `else = (code, body)` option that sends
+/// that response when the check fails, and should be applied directly to async functions
 /// that accept a `&mut Context` parameter.
 #[proc_macro_attribute]
-pub fn is_post_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    is_post_method_handler(item, Position::Prologue)
+pub fn is_post_method(attr: TokenStream, item: TokenStream) -> TokenStream {
+    is_post_method_handler(attr, item, Position::Prologue)
 }
 
 /// Restricts function execution to HTTP PUT requests only.
@@ -299,11 +450,12 @@ pub fn is_post_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// async fn standalone_put_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
+/// The macro optionally takes an `else = code` or `else = (code, body)` option that sends
+/// that response when the check fails, and should be applied directly to async functions
 /// that accept a `&mut Context` parameter.
 #[proc_macro_attribute]
-pub fn is_put_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    is_put_method_handler(item, Position::Prologue)
+pub fn is_put_method(attr: TokenStream, item: TokenStream) -> TokenStream {
+    is_put_method_handler(attr, item, Position::Prologue)
 }
 
 /// Restricts function execution to HTTP DELETE requests only.
@@ -338,11 +490,12 @@ pub fn is_put_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// async fn standalone_delete_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
+/// The macro optionally takes an `else = code` or `else = (code, body)` option that sends
+/// that response when the check fails, and should be applied directly to async functions
 /// that accept a `&mut Context` parameter.
 #[proc_macro_attribute]
-pub fn is_delete_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    is_delete_method_handler(item, Position::Prologue)
+pub fn is_delete_method(attr: TokenStream, item: TokenStream) -> TokenStream {
+    is_delete_method_handler(attr, item, Position::Prologue)
 }
 
 /// Restricts function execution to HTTP PATCH requests only.
@@ -377,11 +530,12 @@ pub fn is_delete_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// async fn standalone_patch_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
+/// The macro optionally takes an `else = code` or `else = (code, body)` option that sends
+/// that response when the check fails, and should be applied directly to async functions
 /// that accept a `&mut Context` parameter.
 #[proc_macro_attribute]
-pub fn is_patch_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    is_patch_method_handler(item, Position::Prologue)
+pub fn is_patch_method(attr: TokenStream, item: TokenStream) -> TokenStream {
+    is_patch_method_handler(attr, item, Position::Prologue)
 }
 
 /// Restricts function execution to HTTP HEAD requests only.
@@ -416,11 +570,12 @@ pub fn is_patch_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// async fn standalone_head_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
+/// The macro optionally takes an `else = code` or `else = (code, body)` option that sends
+/// that response when the check fails, and should be applied directly to async functions
 /// that accept a `&mut Context` parameter.
 #[proc_macro_attribute]
-pub fn is_head_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    is_head_method_handler(item, Position::Prologue)
+pub fn is_head_method(attr: TokenStream, item: TokenStream) -> TokenStream {
+    is_head_method_handler(attr, item, Position::Prologue)
 }
 
 /// Restricts function execution to HTTP OPTIONS requests only.
@@ -455,11 +610,12 @@ pub fn is_head_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// async fn standalone_options_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
+/// The macro optionally takes an `else = code` or `else = (code, body)` option that sends
+/// that response when the check fails, and should be applied directly to async functions
 /// that accept a `&mut Context` parameter.
 #[proc_macro_attribute]
-pub fn is_options_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    is_options_method_handler(item, Position::Prologue)
+pub fn is_options_method(attr: TokenStream, item: TokenStream) -> TokenStream {
+    is_options_method_handler(attr, item, Position::Prologue)
 }
 
 /// Restricts function execution to HTTP CONNECT requests only.
@@ -494,11 +650,12 @@ pub fn is_options_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// async fn standalone_connect_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
+/// The macro optionally takes an `else = code` or `else = (code, body)` option that sends
+/// that response when the check fails, and should be applied directly to async functions
 /// that accept a `&mut Context` parameter.
 #[proc_macro_attribute]
-pub fn is_connect_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    is_connect_method_handler(item, Position::Prologue)
+pub fn is_connect_method(attr: TokenStream, item: TokenStream) -> TokenStream {
+    is_connect_method_handler(attr, item, Position::Prologue)
 }
 
 /// Restricts function execution to HTTP TRACE requests only.
@@ -533,11 +690,12 @@ pub fn is_connect_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// async fn standalone_trace_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
+/// The macro optionally takes an `else = code` or `else = (code, body)` option that sends
+/// that response when the check fails, and should be applied directly to async functions
 /// that accept a `&mut Context` parameter.
 #[proc_macro_attribute]
-pub fn is_trace_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    is_trace_method_handler(item, Position::Prologue)
+pub fn is_trace_method(attr: TokenStream, item: TokenStream) -> TokenStream {
+    is_trace_method_handler(attr, item, Position::Prologue)
 }
 
 /// Restricts function execution to unknown HTTP methods only.
@@ -577,11 +735,12 @@ pub fn is_trace_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// async fn standalone_is_unknown_method_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
+/// The macro optionally takes an `else = code` or `else = (code, body)` option that sends
+/// that response when the check fails, and should be applied directly to async functions
 /// that accept a `&mut Context` parameter.
 #[proc_macro_attribute]
-pub fn is_unknown_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    is_unknown_method_handler(item, Position::Prologue)
+pub fn is_unknown_method(attr: TokenStream, item: TokenStream) -> TokenStream {
+    is_unknown_method_handler(attr, item, Position::Prologue)
 }
 
 /// Allows function to handle multiple HTTP methods.
@@ -620,13 +779,116 @@ pub fn is_unknown_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// async fn standalone_methods_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro accepts a comma-separated list of HTTP method names (lowercase) and should be
-/// applied to async functions that accept a `&mut Context` parameter.
+/// Sending a response when the check fails:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::methods;
+///
+/// #[methods(get, post, else = (405, "method not allowed"))]
+/// async fn responding_methods_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// The macro accepts a comma-separated list of HTTP method names (lowercase), optionally
+/// followed by an `else = code` or `else = (code, body)` option that sends that response
+/// when the method does not match, and should be applied to async functions that accept
+/// a `&mut Context` parameter.
 #[proc_macro_attribute]
 pub fn methods(attr: TokenStream, item: TokenStream) -> TokenStream {
     methods_macro(attr, item, Position::Prologue)
 }
 
+/// Restricts function execution to requests with specific media types.
+///
+/// This attribute macro ensures the decorated function only executes when the `Content-Type`
+/// header of the incoming request matches one of the specified media types. Parameters such as
+/// `charset` are ignored, the comparison is case-insensitive, and `type/*` or `type/*+suffix`
+/// patterns match whole families of media types.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// #[route("/consumes")]
+/// struct Consumes;
+///
+/// impl ServerHook for Consumes {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[consumes("application/json", "application/*+json")]
+///     #[response_body("json accepted")]
+///     async fn handle(self, stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// #[consumes("multipart/form-data", respond = (415, "expected a form upload"))]
+/// async fn standalone_consumes_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// Requests that do not match, including those without a `Content-Type` header, are answered
+/// with `415 Unsupported Media Type`. A trailing `respond = ...` or `else = ...` option, given as
+/// `code` or `(code, body)`, replaces that response.
+#[proc_macro_attribute]
+pub fn consumes(attr: TokenStream, item: TokenStream) -> TokenStream {
+    consumes_macro(attr, item, Position::Prologue)
+}
+
+/// Negotiates the response media type against the `Accept` header.
+///
+/// This attribute macro picks the specified media type with the highest quality in the `Accept`
+/// header, using the most specific matching range for each media type and preferring earlier
+/// media types on ties. A missing `Accept` header accepts the first media type. The chosen media
+/// type is set as the response `Content-Type` and can be bound with `capture = variable`.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// #[route("/produces")]
+/// struct Produces;
+///
+/// impl ServerHook for Produces {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[produces("application/json", "text/html", capture = media_type)]
+///     async fn handle(self, stream: &mut Stream, ctx: &mut Context) -> Status {
+///         let body: &str = if media_type == "text/html" { "<p>ok</p>" } else { "{\"ok\":true}" };
+///         ctx.get_mut_response().set_body(body);
+///         Status::Continue
+///     }
+/// }
+///
+/// #[produces("text/plain", respond = (406, "only text/plain is available"))]
+/// async fn standalone_produces_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// The captured variable is a `String`. When nothing is acceptable the request is answered with
+/// `406 Not Acceptable`. A trailing `respond = ...` or `else = ...` option, given as `code` or
+/// `(code, body)`, replaces that response.
+///
+/// Media types are set as the response `Content-Type`, so literal media types with a `*` are
+/// rejected:
+///
+/// ```rust,compile_fail
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// #[produces("application/*")]
+/// async fn invalid_produces_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+#[proc_macro_attribute]
+pub fn produces(attr: TokenStream, item: TokenStream) -> TokenStream {
+    produces_macro(attr, item, Position::Prologue)
+}
+
 /// Restricts function execution to HTTP/0.9 requests only.
 ///
 /// This attribute macro ensures the decorated function only executes for HTTP/0.9
@@ -659,11 +921,12 @@ pub fn methods(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// async fn standalone_http0_9_version_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
+/// The macro optionally takes an `else = code` or `else = (code, body)` option that sends
+/// that response when the check fails, and should be applied directly to async functions
 /// that accept a `&mut Context` parameter.
 #[proc_macro_attribute]
-pub fn is_http0_9_version(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    is_http0_9_version_macro(item, Position::Prologue)
+pub fn is_http0_9_version(attr: TokenStream, item: TokenStream) -> TokenStream {
+    is_http0_9_version_macro(attr, item, Position::Prologue)
 }
 
 /// Restricts function execution to HTTP/1.0 requests only.
@@ -698,11 +961,12 @@ pub fn is_http0_9_version(_attr: TokenStream, item: TokenStream) -> TokenStream
 /// async fn standalone_http1_0_version_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
+/// The macro optionally takes an `else = code` or `else = (code, body)` option that sends
+/// that response when the check fails, and should be applied directly to async functions
 /// that accept a `&mut Context` parameter.
 #[proc_macro_attribute]
-pub fn is_http1_0_version(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    is_http1_0_version_macro(item, Position::Prologue)
+pub fn is_http1_0_version(attr: TokenStream, item: TokenStream) -> TokenStream {
+    is_http1_0_version_macro(attr, item, Position::Prologue)
 }
 
 /// Restricts function execution to HTTP/1.1 requests only.
@@ -737,11 +1001,12 @@ pub fn is_http1_0_version(_attr: TokenStream, item: TokenStream) -> TokenStream
 /// async fn standalone_http1_1_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
+/// The macro optionally takes an `else = code` or `else = (code, body)` option that sends
+/// that response when the check fails, and should be applied directly to async functions
 /// that accept a `&mut Context` parameter.
 #[proc_macro_attribute]
-pub fn is_http1_1_version(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    is_http1_1_version_macro(item, Position::Prologue)
+pub fn is_http1_1_version(attr: TokenStream, item: TokenStream) -> TokenStream {
+    is_http1_1_version_macro(attr, item, Position::Prologue)
 }
 
 /// Restricts function execution to HTTP/2 requests only.
@@ -776,11 +1041,23 @@ pub fn is_http1_1_version(_attr: TokenStream, item: TokenStream) -> TokenStream
 /// async fn standalone_http2_version_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
+/// Sending a response when the check fails:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::is_http2_version;
+///
+/// #[is_http2_version(else = (505, "HTTP/2 required"))]
+/// async fn responding_is_http2_version_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// The macro optionally takes an `else = code` or `else = (code, body)` option that sends
+/// that response when the check fails, and should be applied directly to async functions
 /// that accept a `&mut Context` parameter.
 #[proc_macro_attribute]
-pub fn is_http2_version(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    is_http2_version_macro(item, Position::Prologue)
+pub fn is_http2_version(attr: TokenStream, item: TokenStream) -> TokenStream {
+    is_http2_version_macro(attr, item, Position::Prologue)
 }
 
 /// Restricts function execution to HTTP/3 requests only.
@@ -815,11 +1092,12 @@ pub fn is_http2_version(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// async fn standalone_http3_version_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
+/// The macro optionally takes an `else = code` or `else = (code, body)` option that sends
+/// that response when the check fails, and should be applied directly to async functions
 /// that accept a `&mut Context` parameter.
 #[proc_macro_attribute]
-pub fn is_http3_version(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    is_http3_version_macro(item, Position::Prologue)
+pub fn is_http3_version(attr: TokenStream, item: TokenStream) -> TokenStream {
+    is_http3_version_macro(attr, item, Position::Prologue)
 }
 
 /// Restricts function execution to HTTP/1.1 or higher protocol versions.
@@ -854,11 +1132,12 @@ pub fn is_http3_version(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// async fn standalone_http1_1_or_higher_version_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
+/// The macro optionally takes an `else = code` or `else = (code, body)` option that sends
+/// that response when the check fails, and should be applied directly to async functions
 /// that accept a `&mut Context` parameter.
 #[proc_macro_attribute]
-pub fn is_http1_1_or_higher_version(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    is_http1_1_or_higher_version_macro(item, Position::Prologue)
+pub fn is_http1_1_or_higher_version(attr: TokenStream, item: TokenStream) -> TokenStream {
+    is_http1_1_or_higher_version_macro(attr, item, Position::Prologue)
 }
 
 /// Restricts function execution to standard HTTP requests only.
@@ -893,11 +1172,12 @@ pub fn is_http1_1_or_higher_version(_attr: TokenStream, item: TokenStream) -> To
 /// async fn standalone_http_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
+/// The macro optionally takes an `else = code` or `else = (code, body)` option that sends
+/// that response when the check fails, and should be applied directly to async functions
 /// that accept a `&mut Context` parameter.
 #[proc_macro_attribute]
-pub fn is_http_version(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    is_http_version_macro(item, Position::Prologue)
+pub fn is_http_version(attr: TokenStream, item: TokenStream) -> TokenStream {
+    is_http_version_macro(attr, item, Position::Prologue)
 }
 
 /// Restricts function execution to requests with unknown HTTP versions only.
@@ -932,11 +1212,12 @@ pub fn is_http_version(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// async fn standalone_unknown_version_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
+/// The macro optionally takes an `else = code` or `else = (code, body)` option that sends
+/// that response when the check fails, and should be applied directly to async functions
 /// that accept a `&mut Context` parameter.
 #[proc_macro_attribute]
-pub fn is_unknown_version(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    is_unknown_version_macro(item, Position::Prologue)
+pub fn is_unknown_version(attr: TokenStream, item: TokenStream) -> TokenStream {
+    is_unknown_version_macro(attr, item, Position::Prologue)
 }
 
 /// Restricts function execution to WebSocket upgrade requests only.
@@ -975,11 +1256,23 @@ pub fn is_unknown_version(_attr: TokenStream, item: TokenStream) -> TokenStream
 /// async fn standalone_ws_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
+/// Sending a response when the check fails:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::is_ws_upgrade_type;
+///
+/// #[is_ws_upgrade_type(else = (426, "upgrade required"))]
+/// async fn responding_is_ws_upgrade_type_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// The macro optionally takes an `else = code` or `else = (code, body)` option that sends
+/// that response when the check fails, and should be applied directly to async functions
 /// that accept a `&mut Context` parameter.
 #[proc_macro_attribute]
-pub fn is_ws_upgrade_type(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    is_ws_upgrade_type_macro(item, Position::Prologue)
+pub fn is_ws_upgrade_type(attr: TokenStream, item: TokenStream) -> TokenStream {
+    is_ws_upgrade_type_macro(attr, item, Position::Prologue)
 }
 
 /// Restricts function execution to HTTP/2 Cleartext (is_h2c_upgrade_type) requests only.
@@ -1014,11 +1307,12 @@ pub fn is_ws_upgrade_type(_attr: TokenStream, item: TokenStream) -> TokenStream
 /// async fn standalone_h2c_upgrade_type_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
+/// The macro optionally takes an `else = code` or `else = (code, body)` option that sends
+/// that response when the check fails, and should be applied directly to async functions
 /// that accept a `&mut Context` parameter.
 #[proc_macro_attribute]
-pub fn is_h2c_upgrade_type(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    is_h2c_upgrade_type_macro(item, Position::Prologue)
+pub fn is_h2c_upgrade_type(attr: TokenStream, item: TokenStream) -> TokenStream {
+    is_h2c_upgrade_type_macro(attr, item, Position::Prologue)
 }
 
 /// Restricts function execution to TLS-encrypted requests only.
@@ -1053,11 +1347,12 @@ pub fn is_h2c_upgrade_type(_attr: TokenStream, item: TokenStream) -> TokenStream
 /// async fn standalone_tls_upgrade_type_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
+/// The macro optionally takes an `else = code` or `else = (code, body)` option that sends
+/// that response when the check fails, and should be applied directly to async functions
 /// that accept a `&mut Context` parameter.
 #[proc_macro_attribute]
-pub fn is_tls_upgrade_type(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    is_tls_upgrade_type_macro(item, Position::Prologue)
+pub fn is_tls_upgrade_type(attr: TokenStream, item: TokenStream) -> TokenStream {
+    is_tls_upgrade_type_macro(attr, item, Position::Prologue)
 }
 
 /// Restricts function execution to requests with unknown protocol upgrade types only.
@@ -1092,17 +1387,19 @@ pub fn is_tls_upgrade_type(_attr: TokenStream, item: TokenStream) -> TokenStream
 /// async fn standalone_unknown_upgrade_type_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
+/// The macro optionally takes an `else = code` or `else = (code, body)` option that sends
+/// that response when the check fails, and should be applied directly to async functions
 /// that accept a `&mut Context` parameter.
 #[proc_macro_attribute]
-pub fn is_unknown_upgrade_type(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    is_unknown_upgrade_type_macro(item, Position::Prologue)
+pub fn is_unknown_upgrade_type(attr: TokenStream, item: TokenStream) -> TokenStream {
+    is_unknown_upgrade_type_macro(attr, item, Position::Prologue)
 }
 
-/// Sets the HTTP status code for the response.
+/// Completes the WebSocket opening handshake before the function body runs.
 ///
-/// This attribute macro configures the HTTP status code that will be sent with the response.
-/// The status code can be provided as a numeric literal or a global constant.
+/// This attribute macro validates the `Upgrade`, `Connection`, `Sec-WebSocket-Key` and
+/// `Sec-WebSocket-Version` headers, generates the `Sec-WebSocket-Accept` key and sends the
+/// `101 Switching Protocols` response, so the body can read frames right away.
 ///
 /// # Usage
 ///
@@ -1110,82 +1407,144 @@ pub fn is_unknown_upgrade_type(_attr: TokenStream, item: TokenStream) -> TokenSt
 /// use hyperlane::*;
 /// use hyperlane_macros::*;
 ///
-/// const CUSTOM_STATUS_CODE: i32 = 200;
-///
-/// #[route("/response_status_code")]
-/// struct ResponseStatusCode;
+/// #[route("/ws_upgrade")]
+/// struct WebsocketUpgrade;
 ///
-/// impl ServerHook for ResponseStatusCode {
+/// impl ServerHook for WebsocketUpgrade {
 ///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
 ///         Self
 ///     }
 ///
-///     #[response_status_code(CUSTOM_STATUS_CODE)]
-///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+///     #[ws_upgrade]
+///     async fn handle(self, stream: &mut Stream, ctx: &mut Context) -> Status {
+///         while let Ok(body) = stream.try_get_websocket_request().await {
+///             let body_list: Vec<ResponseBody> = WebSocketFrame::create_frame_list(&body);
+///             stream.send_list(body_list).await;
+///         }
+///         Status::Continue
+///     }
 /// }
 ///
-/// impl ResponseStatusCode {
-///     #[response_status_code(CUSTOM_STATUS_CODE)]
-///     async fn response_status_code_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// #[ws_upgrade(else = (400, "invalid websocket handshake"))]
+/// async fn standalone_ws_upgrade_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// Negotiating a subprotocol:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// #[ws_upgrade(protocols("graphql-ws", "json.v1"))]
+/// async fn negotiated_ws_upgrade_handler(stream: &mut Stream, ctx: &mut Context) -> Status {
+///     let _: &String = &negotiated_protocol;
+///     Status::Continue
 /// }
 ///
-/// #[response_status_code(200)]
-/// async fn standalone_response_status_code_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// #[ws_upgrade(protocols("json.v1"), capture = protocol, else = (400, "json.v1 required"))]
+/// async fn captured_ws_upgrade_handler(stream: &mut Stream, ctx: &mut Context) -> Status {
+///     let _: &String = &protocol;
+///     Status::Continue
+/// }
 /// ```
 ///
-/// The macro accepts a numeric HTTP status code or a global constant
-/// and should be applied to async functions that accept a `&mut Context` parameter.
+/// A malformed handshake is answered with `400 Bad Request`, or with the response given by an
+/// `else = code` or `else = (code, body)` option, and the function returns `Status::Reject`.
+/// An unsupported version also advertises `Sec-WebSocket-Version: 13`.
+///
+/// With `protocols(...)` the first listed subprotocol the client offers in
+/// `Sec-WebSocket-Protocol` is echoed in the handshake and bound as a `String` to the
+/// `capture` variable, `negotiated_protocol` by default. A client offering none of them is
+/// rejected with `400 Bad Request` and an explanatory body, or with the `else` response.
+/// Offered extensions are declined. The macro should be applied directly to async functions
+/// that accept `&mut Stream` and `&mut Context` parameters.
 #[proc_macro_attribute]
-pub fn response_status_code(attr: TokenStream, item: TokenStream) -> TokenStream {
-    response_status_code_macro(attr, item, Position::Prologue)
+pub fn ws_upgrade(attr: TokenStream, item: TokenStream) -> TokenStream {
+    ws_upgrade_macro(attr, item, Position::Prologue)
 }
 
-/// Sets the HTTP reason phrase for the response.
+/// Declares a WebSocket room registry.
 ///
-/// This attribute macro configures the HTTP reason phrase that accompanies the status code.
-/// The reason phrase can be provided as a string literal or a global constant.
+/// This function-like macro expands to a lazily initialized static holding the members of
+/// every room, so handlers can join rooms with `ws_join` and reach their members with
+/// `ws_broadcast` and `ws_room_size`.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane_macros::*;
+///
+/// ws_rooms!(CHAT_ROOMS);
+/// ws_rooms!(pub(crate) GAME_ROOMS);
+/// ```
+///
+/// The argument is the registry name, optionally preceded by a visibility.
+#[proc_macro]
+pub fn ws_rooms(input: TokenStream) -> TokenStream {
+    ws_rooms_macro(input)
+}
+
+/// Joins the WebSocket connection to rooms before the function body runs.
+///
+/// This attribute macro registers the connection under each room of the given registry.
+/// A `try_get_websocket_request` loop placed above it writes the messages broadcast to those
+/// rooms to the stream while it waits for the next frame, and the connection leaves its
+/// rooms once the loop ends and the function returns.
 ///
 /// # Usage
 ///
 /// ```rust
 /// use hyperlane::*;
 /// use hyperlane_macros::*;
+/// use hyperlane_macros::try_get_websocket_request;
 ///
-/// const CUSTOM_REASON: &str = "Accepted";
+/// ws_rooms!(CHAT_ROOMS);
 ///
-/// #[route("/response_reason")]
-/// struct ResponseReason;
+/// #[route("/ws/rooms/{room}")]
+/// struct RoomChat;
 ///
-/// impl ServerHook for ResponseReason {
+/// impl ServerHook for RoomChat {
 ///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
 ///         Self
 ///     }
 ///
-///     #[response_reason_phrase(CUSTOM_REASON)]
-///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+///     #[try_get_websocket_request(body)]
+///     #[ws_join(CHAT_ROOMS => ctx.try_get_route_param("room").unwrap_or_default())]
+///     #[ws_upgrade]
+///     async fn handle(self, stream: &mut Stream, ctx: &mut Context) -> Status {
+///         let room: String = ctx.try_get_route_param("room").unwrap_or_default();
+///         let members: usize = ws_room_size!(CHAT_ROOMS, room);
+///         let delivered: usize = ws_broadcast!(CHAT_ROOMS, room, &body, exclude = ws_room_member);
+///         let _ = (members, delivered);
+///     }
 /// }
 ///
-/// impl ResponseReason {
-///     #[response_reason_phrase(CUSTOM_REASON)]
-///     async fn response_reason_phrase_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// #[try_get_websocket_request(body)]
+/// #[ws_join(CHAT_ROOMS => "lobby", CHAT_ROOMS => "announcements")]
+/// async fn lobby_websocket_handler(stream: &mut Stream, ctx: &mut Context) -> Status {
+///     ws_broadcast!(CHAT_ROOMS, "lobby", &body);
 /// }
 ///
-/// #[response_reason_phrase("OK")]
-/// async fn standalone_response_reason_phrase_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// async fn announce(stream: &mut Stream, ctx: &mut Context) {
+///     ws_broadcast!(CHAT_ROOMS, "announcements", "server restarting");
+/// }
 /// ```
 ///
-/// The macro accepts a string literal or global constant for the reason phrase and should be
-/// applied to async functions that accept a `&mut Context` parameter.
+/// Each `REGISTRY => room` pair joins `room`, converted with `ToString`, in a registry
+/// declared with `ws_rooms!`. All pairs must name the same registry. The connection's member
+/// id in that registry, a `u64` counted up for every join, is bound to `ws_room_member`.
+/// `ws_join` must be placed below `try_get_websocket_request`, and below `ws_upgrade` when the
+/// handshake should complete before joining. The macro should be applied directly to async
+/// functions that accept `&mut Stream` and `&mut Context` parameters.
 #[proc_macro_attribute]
-pub fn response_reason_phrase(attr: TokenStream, item: TokenStream) -> TokenStream {
-    response_reason_phrase_macro(attr, item, Position::Prologue)
+pub fn ws_join(attr: TokenStream, item: TokenStream) -> TokenStream {
+    ws_join_macro(attr, item, Position::Prologue)
 }
 
-/// Sets or replaces a specific HTTP response header.
+/// Broadcasts a WebSocket message to a room.
 ///
-/// This attribute macro configures a specific HTTP response header that will be sent with the response.
-/// Both the header name and value can be provided as string literals or global constants.
-/// Use `"key", "value"` to set a header (add to existing headers) or `"key" => "value"` to replace a header (overwrite existing).
+/// This function-like macro frames the message once and queues it for every member of the
+/// room, evaluating to the number of members it was queued for.
 ///
 /// # Usage
 ///
@@ -1193,56 +1552,692 @@ pub fn response_reason_phrase(attr: TokenStream, item: TokenStream) -> TokenStre
 /// use hyperlane::*;
 /// use hyperlane_macros::*;
 ///
-/// const CUSTOM_HEADER_NAME: &str = "X-Custom-Header";
-/// const CUSTOM_HEADER_VALUE: &str = "custom-value";
+/// ws_rooms!(CHAT_ROOMS);
 ///
-/// #[route("/response_header")]
-/// struct ResponseHeader;
+/// fn notify(sender: u64) {
+///     let delivered: usize = ws_broadcast!(CHAT_ROOMS, "lobby", "hello");
+///     let others: usize = ws_broadcast!(CHAT_ROOMS, 42, b"ping".to_vec(), exclude = sender);
+///     let _ = (delivered, others);
+/// }
+/// ```
 ///
-/// impl ServerHook for ResponseHeader {
-///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
-///         Self
-///     }
+/// The arguments are the registry, the room, converted with `ToString`, and any message
+/// implementing `AsRef<[u8]>`. `exclude = member` skips the member with that id, such as the
+/// `ws_room_member` bound by `ws_join`, so a member can relay a message to everyone else in its room. Each member queues at most 64
+/// messages that its receive loop has not written yet. Messages for a member whose queue is full,
+/// such as a client that stopped reading, are dropped for that member and not counted, so one slow
+/// client neither grows memory without limit nor holds up the others.
+#[proc_macro]
+pub fn ws_broadcast(input: TokenStream) -> TokenStream {
+    ws_broadcast_macro(input)
+}
+
+/// Counts the members of a WebSocket room.
 ///
-///     #[response_header(CUSTOM_HEADER_NAME => CUSTOM_HEADER_VALUE)]
-///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
-/// }
+/// # Usage
 ///
-/// impl ResponseHeader {
-///     #[response_header(CUSTOM_HEADER_NAME => CUSTOM_HEADER_VALUE)]
-///     async fn response_header_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
-/// }
+/// ```rust
+/// use hyperlane_macros::*;
 ///
-/// #[route("/response_header")]
-/// struct ResponseHeaderTest;
+/// ws_rooms!(CHAT_ROOMS);
 ///
-/// impl ServerHook for ResponseHeaderTest {
-///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
-///         Self
-///     }
+/// fn lobby_size() -> usize {
+///     ws_room_size!(CHAT_ROOMS, "lobby")
+/// }
+/// ```
 ///
-///     #[response_body("Testing header set and replace operations")]
+/// The arguments are the registry and the room, converted with `ToString`. A room without
+/// members counts zero.
+#[proc_macro]
+pub fn ws_room_size(input: TokenStream) -> TokenStream {
+    ws_room_size_macro(input)
+}
+
+/// Routes WebSocket messages to methods by the type tag of their JSON envelope.
+///
+/// This attribute macro adds a `dispatch` method to an inherent impl block. It decodes a
+/// message body as a JSON object, reads its `type` field and awaits the method marked with
+/// the matching `#[on("...")]`, passing the message decoded into that method's message type.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::try_get_websocket_request;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Join {
+///     room: String,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Say {
+///     text: String,
+/// }
+///
+/// #[derive(Default)]
+/// struct ChatHandlers {
+///     room: Option<String>,
+/// }
+///
+/// #[ws_dispatch]
+/// impl ChatHandlers {
+///     #[on("join")]
+///     async fn join(&mut self, msg: Join, stream: &mut Stream, ctx: &mut Context) {
+///         self.room = Some(msg.room);
+///     }
+///
+///     #[on("say")]
+///     async fn say(&mut self, msg: Say, stream: &mut Stream, ctx: &mut Context) {
+///         stream.send_list(WebSocketFrame::create_frame_list(&msg.text)).await;
+///     }
+///
+///     #[fallback]
+///     async fn unknown(&mut self, msg: serde_json::Value, stream: &mut Stream, ctx: &mut Context) {
+///         stream.send_list(WebSocketFrame::create_frame_list("unknown message type")).await;
+///     }
+/// }
+///
+/// #[route("/ws/dispatch")]
+/// struct Chat {
+///     handlers: ChatHandlers,
+/// }
+///
+/// impl ServerHook for Chat {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self {
+///             handlers: ChatHandlers::default(),
+///         }
+///     }
+///
+///     #[try_get_websocket_request(body)]
+///     #[ws_upgrade]
+///     async fn handle(mut self, stream: &mut Stream, ctx: &mut Context) -> Status {
+///         if self.handlers.dispatch(&body, stream, ctx).await.is_err() {
+///             stream.send_list(WebSocketFrame::create_frame_list("invalid message")).await;
+///         }
+///     }
+/// }
+///
+/// struct Commands;
+///
+/// #[ws_dispatch(tag = "op")]
+/// impl Commands {
+///     #[on("ping")]
+///     async fn ping(&self, msg: serde_json::Value, stream: &mut Stream, ctx: &mut Context) -> Status {
+///         Status::Continue
+///     }
+/// }
+/// ```
+///
+/// The tag field, `type` by default or the name given by `tag = "..."`, is removed before the
+/// message is decoded. The `#[fallback]` method receives the whole envelope as a
+/// `serde_json::Value` when the tag is missing or matches no method. `dispatch(&body, stream, ctx)`
+/// returns the awaited method's result in a `serde_json::Result`, failing for a body that is not
+/// JSON, a message that does not decode, or an unmatched tag without a fallback. Routed methods
+/// must be async, borrow `self`, take the message, the stream and the context in that order, and
+/// share one return type.
+#[proc_macro_attribute]
+pub fn ws_dispatch(attr: TokenStream, item: TokenStream) -> TokenStream {
+    ws_dispatch_macro(attr, item)
+}
+
+/// Declares a complete WebSocket endpoint on a struct.
+///
+/// This attribute macro registers the struct as the route handler for the path, like `route`,
+/// and implements `ServerHook` for it. Requests that are not a WebSocket upgrade are rejected
+/// without a response, as `is_ws_upgrade_type` does. Each connection completes the handshake as
+/// `ws_upgrade` does, then the struct's `on_message` method is awaited for every message read by
+/// the `try_get_websocket_request` loop.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use serde::Deserialize;
+///
+/// #[websocket("/ws/echo")]
+/// struct Echo;
+///
+/// impl Echo {
+///     async fn on_message(&mut self, msg: RequestBody, stream: &mut Stream, ctx: &mut Context) {
+///         stream.send_list(WebSocketFrame::create_frame_list(&msg)).await;
+///     }
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct ChatMessage {
+///     text: String,
+/// }
+///
+/// #[websocket("/ws/chat", msg: ChatMessage, ping_interval = "30s", idle_timeout = "2m")]
+/// #[derive(Default)]
+/// struct Chat {
+///     received: usize,
+/// }
+///
+/// impl Chat {
+///     async fn on_open(&mut self, stream: &mut Stream, ctx: &mut Context) {
+///         stream.send_list(WebSocketFrame::create_frame_list("welcome")).await;
+///     }
+///
+///     async fn on_message(&mut self, msg: ChatMessage, stream: &mut Stream, ctx: &mut Context) {
+///         self.received += 1;
+///         stream.send_list(WebSocketFrame::create_frame_list(&msg.text)).await;
+///     }
+///
+///     async fn on_close(&mut self, stream: &mut Stream, ctx: &mut Context) {
+///         let _ = self.received;
+///     }
+/// }
+/// ```
+///
+/// The path may be followed by any option of `try_get_websocket_request` except `on_close`,
+/// such as `msg: Type` to decode each message as JSON, `ping_interval`, `idle_timeout` or
+/// `on_error`. Without a typed variable, `on_message` receives the `RequestBody`.
+///
+/// `on_open` runs after the handshake and `on_close` once the loop ends for any reason; both are
+/// optional. An upgrade request with a malformed handshake is answered with `400 Bad Request`.
+/// A unit struct is constructed directly and any other struct through `Default`, once per
+/// connection.
+#[proc_macro_attribute]
+pub fn websocket(attr: TokenStream, item: TokenStream) -> TokenStream {
+    websocket_macro(attr, item)
+}
+
+/// Sets the HTTP status code for the response.
+///
+/// This attribute macro configures the HTTP status code that will be sent with the response.
+/// The status code can be provided as a numeric literal or a global constant.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// const CUSTOM_STATUS_CODE: i32 = 200;
+///
+/// #[route("/response_status_code")]
+/// struct ResponseStatusCode;
+///
+/// impl ServerHook for ResponseStatusCode {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[response_status_code(CUSTOM_STATUS_CODE)]
+///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// impl ResponseStatusCode {
+///     #[response_status_code(CUSTOM_STATUS_CODE)]
+///     async fn response_status_code_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// #[response_status_code(200)]
+/// async fn standalone_response_status_code_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// Literal status codes are checked at compile time. Values outside `100..=599` are
+/// rejected, non-standard codes produce a warning, and a literal `response_reason_phrase`
+/// on the same function produces a warning when it differs from the standard phrase:
+///
+/// ```rust,compile_fail
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::response_status_code;
+///
+/// #[response_status_code(2000)]
+/// async fn invalid_response_status_code_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// The macro accepts a numeric HTTP status code or a global constant
+/// and should be applied to async functions that accept a `&mut Context` parameter.
+#[proc_macro_attribute]
+pub fn response_status_code(attr: TokenStream, item: TokenStream) -> TokenStream {
+    response_status_code_macro(attr, item, Position::Prologue)
+}
+
+/// Sets the HTTP reason phrase for the response.
+///
+/// This attribute macro configures the HTTP reason phrase that accompanies the status code.
+/// The reason phrase can be provided as a string literal or a global constant.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// const CUSTOM_REASON: &str = "Accepted";
+///
+/// #[route("/response_reason")]
+/// struct ResponseReason;
+///
+/// impl ServerHook for ResponseReason {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[response_reason_phrase(CUSTOM_REASON)]
+///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// impl ResponseReason {
+///     #[response_reason_phrase(CUSTOM_REASON)]
+///     async fn response_reason_phrase_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// #[response_reason_phrase("OK")]
+/// async fn standalone_response_reason_phrase_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// The macro accepts a string literal or global constant for the reason phrase and should be
+/// applied to async functions that accept a `&mut Context` parameter.
+#[proc_macro_attribute]
+pub fn response_reason_phrase(attr: TokenStream, item: TokenStream) -> TokenStream {
+    response_reason_phrase_macro(attr, item, Position::Prologue)
+}
+
+/// Sets or replaces a specific HTTP response header.
+///
+/// This attribute macro configures a specific HTTP response header that will be sent with the response.
+/// Both the header name and value can be provided as string literals or global constants.
+/// Use `"key", "value"` to set a header (add to existing headers) or `"key" => "value"` to replace a header (overwrite existing).
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// const CUSTOM_HEADER_NAME: &str = "X-Custom-Header";
+/// const CUSTOM_HEADER_VALUE: &str = "custom-value";
+///
+/// #[route("/response_header")]
+/// struct ResponseHeader;
+///
+/// impl ServerHook for ResponseHeader {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[response_header(CUSTOM_HEADER_NAME => CUSTOM_HEADER_VALUE)]
+///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// impl ResponseHeader {
+///     #[response_header(CUSTOM_HEADER_NAME => CUSTOM_HEADER_VALUE)]
+///     async fn response_header_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// #[route("/response_header")]
+/// struct ResponseHeaderTest;
+///
+/// impl ServerHook for ResponseHeaderTest {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[response_body("Testing header set and replace operations")]
 ///     #[response_header("X-Add-Header", "add-value")]
 ///     #[response_header("X-Set-Header" => "set-value")]
 ///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// }
 ///
-/// #[response_header("X-Custom" => "value")]
-/// async fn standalone_response_header_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// #[response_header("X-Custom" => "value")]
+/// async fn standalone_response_header_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// The macro accepts header name and header value, both can be string literals or global constants.
+/// Use `"key", "value"` for setting headers and `"key" => "value"` for replacing headers.
+/// Should be applied to async functions that accept a `&mut Context` parameter.
+#[proc_macro_attribute]
+pub fn response_header(attr: TokenStream, item: TokenStream) -> TokenStream {
+    response_header_macro(attr, item, Position::Prologue)
+}
+
+/// Removes specific HTTP response headers.
+///
+/// This attribute macro removes one or more headers from the response, leaving all other
+/// headers untouched. Header names can be provided as string literals or global constants.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// #[response_middleware]
+/// struct RemoveResponseHeader;
+///
+/// impl ServerHook for RemoveResponseHeader {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[remove_response_header(SERVER, X_POWERED_BY)]
+///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// impl RemoveResponseHeader {
+///     #[remove_response_header("x-custom-header")]
+///     async fn remove_response_header_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// #[remove_response_header(SERVER)]
+/// async fn standalone_remove_response_header_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// The macro accepts one or more comma-separated header names and should be applied to
+/// async functions that accept a `&mut Context` parameter.
+#[proc_macro_attribute]
+pub fn remove_response_header(attr: TokenStream, item: TokenStream) -> TokenStream {
+    remove_response_header_macro(attr, item, Position::Prologue)
+}
+
+/// Sets a specific HTTP response header only if it is not already present.
+///
+/// This attribute macro leaves an existing header untouched and otherwise sets it to the
+/// given value. Both the header name and value can be provided as string literals or global constants.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// #[response_middleware]
+/// struct ResponseHeaderIfAbsent;
+///
+/// impl ServerHook for ResponseHeaderIfAbsent {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[response_header_if_absent(CONTENT_TYPE => TEXT_PLAIN)]
+///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// impl ResponseHeaderIfAbsent {
+///     #[response_header_if_absent(SERVER => HYPERLANE)]
+///     async fn response_header_if_absent_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// #[response_header_if_absent("x-custom" => "value")]
+/// async fn standalone_response_header_if_absent_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// The macro accepts a `key => value` pair and should be applied to async functions
+/// that accept a `&mut Context` parameter.
+#[proc_macro_attribute]
+pub fn response_header_if_absent(attr: TokenStream, item: TokenStream) -> TokenStream {
+    response_header_if_absent_macro(attr, item, Position::Prologue)
+}
+
+/// Sets the HTTP response body.
+///
+/// This attribute macro configures the HTTP response body that will be sent with the response.
+/// The body content can be provided as a string literal or a global constant.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// const RESPONSE_DATA: &str = "{\"status\": \"success\"}";
+///
+/// #[route("/response_body")]
+/// struct ResponseBody;
+///
+/// impl ServerHook for ResponseBody {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[response_body(&RESPONSE_DATA)]
+///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// impl ResponseBody {
+///     #[response_body(&RESPONSE_DATA)]
+///     async fn response_body_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// #[response_body("standalone response body")]
+/// async fn standalone_response_body_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// The macro accepts a string literal or global constant for the response body and should be
+/// applied to async functions that accept a `&mut Context` parameter.
+#[proc_macro_attribute]
+pub fn response_body(attr: TokenStream, item: TokenStream) -> TokenStream {
+    response_body_macro(attr, item, Position::Prologue)
+}
+
+/// Clears all response headers.
+///
+/// This attribute macro clears all response headers from the response.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// #[route("/clear_response_headers")]
+/// struct ClearResponseHeaders;
+///
+/// impl ServerHook for ClearResponseHeaders {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[prologue_macros(
+///         clear_response_headers,
+///         filter(ctx.get_request().get_method().is_unknown()),
+///         response_body("clear_response_headers")
+///     )]
+///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// impl ClearResponseHeaders {
+///     #[clear_response_headers]
+///     async fn clear_response_headers_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// #[clear_response_headers]
+/// async fn standalone_clear_response_headers_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// The macro should be applied to async functions that accept a `&mut Context` parameter.   
+#[proc_macro_attribute]
+pub fn clear_response_headers(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    clear_response_headers_macro(item, Position::Prologue)
+}
+
+/// Sets the HTTP response version.
+///
+/// This attribute macro configures the HTTP response version that will be sent with the response.
+/// The version can be provided as a variable or code block.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// #[request_middleware]
+/// struct RequestMiddleware;
+///
+/// impl ServerHook for RequestMiddleware {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[epilogue_macros(
+///         response_status_code(200),
+///         response_version(HttpVersion::Http1_1),
+///         response_header(SERVER => HYPERLANE)
+///     )]
+///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// impl RequestMiddleware {
+///     #[response_version(HttpVersion::Http2)]
+///     async fn response_version_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// #[response_version(HttpVersion::Http1_0)]
+/// async fn standalone_response_version_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// String literals are checked at compile time and must name a known HTTP version:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::response_version;
+///
+/// #[response_version("HTTP/1.1")]
+/// async fn literal_response_version_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// ```rust,compile_fail
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::response_version;
+///
+/// #[response_version("HTTP/9")]
+/// async fn invalid_response_version_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// The macro accepts a variable or code block for the response version and should be
+/// applied to async functions that accept a `&mut Context` parameter.
+#[proc_macro_attribute]
+pub fn response_version(attr: TokenStream, item: TokenStream) -> TokenStream {
+    response_version_macro(attr, item, Position::Prologue)
+}
+
+/// Handles closed connection scenarios.
+///
+/// This attribute macro configures the function to handle cases where the connection
+/// has been closed, providing appropriate handling for terminated or disconnected connections.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// #[route("/closed")]
+/// struct ClosedTest;
+///
+/// impl ServerHook for ClosedTest {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[closed]
+///     async fn handle(self, stream: &mut Stream, _: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// impl ClosedTest {
+///     #[closed]
+///     async fn closed_with_ref_self(&self, stream: &mut Stream, _: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// #[closed]
+/// async fn standalone_closed_handler(stream: &mut Stream, _: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// The macro takes no parameters and should be applied directly to async functions
+/// that accept a `&mut Context` parameter.
+#[proc_macro_attribute]
+pub fn closed(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    closed_macro(item, Position::Prologue)
+}
+
+/// Filters requests based on a boolean condition.
+///
+/// The function continues execution only if the provided code block returns `true`.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// #[route("/is_unknown_method")]
+/// struct UnknownMethod;
+///
+/// impl ServerHook for UnknownMethod {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[prologue_macros(
+///         filter(ctx.get_request().get_method().is_unknown()),
+///         response_body("is_unknown_method")
+///     )]
+///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// impl UnknownMethod {
+///     #[filter(true)]
+///     async fn filter_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// #[filter(true)]
+/// async fn standalone_filter_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// Sending a response when the check fails:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::filter;
+///
+/// #[filter(ctx.get_request().get_method().is_get(), else = 405)]
+/// async fn responding_filter_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// Awaiting an async check:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::filter;
+///
+/// async fn is_authorized(ctx: &Context) -> bool {
+///     ctx.get_request().try_get_header_back("authorization").is_some()
+/// }
+///
+/// #[filter(async is_authorized(ctx), else = 401)]
+/// async fn async_filter_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+///
+/// #[filter(is_authorized(ctx).await)]
+/// async fn awaiting_filter_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro accepts header name and header value, both can be string literals or global constants.
-/// Use `"key", "value"` for setting headers and `"key" => "value"` for replacing headers.
-/// Should be applied to async functions that accept a `&mut Context` parameter.
+/// A `respond = ...` or `else = ...` option, given as `code` or `(code, body)`, sends that
+/// response and returns `Status::Reject` when the request is stopped.
+/// A condition prefixed with `async`, or written as an `async { ... }` block, is awaited;
+/// a condition may also use `.await` directly since it runs inside the async handler.
 #[proc_macro_attribute]
-pub fn response_header(attr: TokenStream, item: TokenStream) -> TokenStream {
-    response_header_macro(attr, item, Position::Prologue)
+pub fn filter(attr: TokenStream, item: TokenStream) -> TokenStream {
+    filter_macro(attr, item, Position::Prologue)
 }
 
-/// Sets the HTTP response body.
+/// Rejects requests based on a boolean condition.
 ///
-/// This attribute macro configures the HTTP response body that will be sent with the response.
-/// The body content can be provided as a string literal or a global constant.
+/// The function continues execution only if the provided code block returns `false`.
 ///
 /// # Usage
 ///
@@ -1250,39 +2245,84 @@ pub fn response_header(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// use hyperlane::*;
 /// use hyperlane_macros::*;
 ///
-/// const RESPONSE_DATA: &str = "{\"status\": \"success\"}";
-///
-/// #[route("/response_body")]
-/// struct ResponseBody;
+/// #[response_middleware(2)]
+/// struct ResponseMiddleware2;
 ///
-/// impl ServerHook for ResponseBody {
+/// impl ServerHook for ResponseMiddleware2 {
 ///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
 ///         Self
 ///     }
 ///
-///     #[response_body(&RESPONSE_DATA)]
+///     #[prologue_macros(
+///         reject(ctx.get_request().get_upgrade_type().is_ws())
+///     )]
 ///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// }
 ///
-/// impl ResponseBody {
-///     #[response_body(&RESPONSE_DATA)]
-///     async fn response_body_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// impl ResponseMiddleware2 {
+///     #[reject(false)]
+///     async fn reject_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// }
 ///
-/// #[response_body("standalone response body")]
-/// async fn standalone_response_body_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// #[reject(false)]
+/// async fn standalone_reject_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro accepts a string literal or global constant for the response body and should be
-/// applied to async functions that accept a `&mut Context` parameter.
+/// Sending a response when the check fails:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::reject;
+///
+/// #[reject(ctx.get_request().get_path().starts_with("/admin"), respond = (403, "forbidden"))]
+/// async fn responding_reject_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// Tuples with any other number of elements are rejected:
+///
+/// ```rust,compile_fail
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::reject;
+///
+/// #[reject(true, respond = (403, "forbidden", "again"))]
+/// async fn invalid_responding_reject_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// Awaiting an async check:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::reject;
+///
+/// async fn is_banned(ctx: &Context) -> bool {
+///     ctx.get_request().try_get_query("banned").is_some()
+/// }
+///
+/// #[reject(async is_banned(ctx), respond = (403, "banned"))]
+/// async fn async_reject_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+///
+/// #[reject(async { is_banned(ctx).await })]
+/// async fn async_block_reject_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// A `respond = ...` or `else = ...` option, given as `code` or `(code, body)`, sends that
+/// response and returns `Status::Reject` when the request is stopped.
+/// A condition prefixed with `async`, or written as an `async { ... }` block, is awaited;
+/// a condition may also use `.await` directly since it runs inside the async handler.
 #[proc_macro_attribute]
-pub fn response_body(attr: TokenStream, item: TokenStream) -> TokenStream {
-    response_body_macro(attr, item, Position::Prologue)
+pub fn reject(attr: TokenStream, item: TokenStream) -> TokenStream {
+    reject_macro(attr, item, Position::Prologue)
 }
 
-/// Clears all response headers.
+/// Filters requests with a guard composed of boolean combinators.
 ///
-/// This attribute macro clears all response headers from the response.
+/// The guard accepts `any(...)`, `all(...)` and `not(...)` around the `is_*_method`,
+/// `is_*_version` and `is_*_upgrade_type` checks and the `host(...)`, `referer(...)` and
+/// `origin(...)` checks. Several top-level conditions must all hold. The whole guard is evaluated once
+/// as a single boolean expression.
 ///
 /// # Usage
 ///
@@ -1290,41 +2330,41 @@ pub fn response_body(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// use hyperlane::*;
 /// use hyperlane_macros::*;
 ///
-/// #[route("/clear_response_headers")]
-/// struct ClearResponseHeaders;
+/// #[route("/guard")]
+/// struct Guard;
 ///
-/// impl ServerHook for ClearResponseHeaders {
+/// impl ServerHook for Guard {
 ///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
 ///         Self
 ///     }
 ///
-///     #[prologue_macros(
-///         clear_response_headers,
-///         filter(ctx.get_request().get_method().is_unknown()),
-///         response_body("clear_response_headers")
-///     )]
+///     #[guard(any(is_http2_version, all(is_get_method, host("api.local")), not(is_ws_upgrade_type)))]
+///     #[response_body("guarded")]
 ///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// }
 ///
-/// impl ClearResponseHeaders {
-///     #[clear_response_headers]
-///     async fn clear_response_headers_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// impl Guard {
+///     #[guard(is_get_method, not(referer("http://evil.local")))]
+///     async fn guard_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// }
 ///
-/// #[clear_response_headers]
-/// async fn standalone_clear_response_headers_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// #[guard(any(is_post_method, is_put_method), respond = (405, "method not allowed"))]
+/// async fn standalone_guard_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro should be applied to async functions that accept a `&mut Context` parameter.   
+/// A `respond = ...` or `else = ...` option, given as `code` or `(code, body)`, sends that
+/// response and returns `Status::Reject` when the request is stopped.
 #[proc_macro_attribute]
-pub fn clear_response_headers(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    clear_response_headers_macro(item, Position::Prologue)
+pub fn guard(attr: TokenStream, item: TokenStream) -> TokenStream {
+    guard_macro(attr, item, Position::Prologue)
 }
 
-/// Sets the HTTP response version.
+/// Restricts function execution to requests with a specific host.
 ///
-/// This attribute macro configures the HTTP response version that will be sent with the response.
-/// The version can be provided as a variable or code block.
+/// This attribute macro ensures the decorated function only executes when the incoming request
+/// has a host header that matches one of the specified values. Requests with different or missing host headers will be filtered out.
+/// Hosts are compared case-insensitively with the port removed, and a value such as
+/// `*.example.com` matches every subdomain of `example.com`.
 ///
 /// # Usage
 ///
@@ -1332,42 +2372,69 @@ pub fn clear_response_headers(_attr: TokenStream, item: TokenStream) -> TokenStr
 /// use hyperlane::*;
 /// use hyperlane_macros::*;
 ///
-/// #[request_middleware]
-/// struct RequestMiddleware;
+/// #[route("/host")]
+/// struct Host;
 ///
-/// impl ServerHook for RequestMiddleware {
+/// impl ServerHook for Host {
 ///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
 ///         Self
 ///     }
 ///
-///     #[epilogue_macros(
-///         response_status_code(200),
-///         response_version(HttpVersion::Http1_1),
-///         response_header(SERVER => HYPERLANE)
-///     )]
-///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+///     #[host("localhost")]
+///     #[prologue_macros(response_body("host string literal: localhost"), send)]
+///     async fn handle(self, stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// }
 ///
-/// impl RequestMiddleware {
-///     #[response_version(HttpVersion::Http2)]
-///     async fn response_version_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// impl Host {
+///     #[host("localhost")]
+///     async fn host_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// }
 ///
-/// #[response_version(HttpVersion::Http1_0)]
-/// async fn standalone_response_version_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// #[host("localhost")]
+/// async fn standalone_host_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro accepts a variable or code block for the response version and should be
+/// Sending a response when the check fails:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::host;
+///
+/// #[host("api.example.com", else = (421, "misdirected request"))]
+/// async fn responding_host_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// Capturing the subdomain matched by a wildcard:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::host;
+///
+/// #[host("*.example.com", "*.example.org", capture = tenant)]
+/// async fn tenant_host_handler(_: &mut Stream, ctx: &mut Context) -> Status {
+///     let tenant: String = tenant;
+///     Status::Continue
+/// }
+/// ```
+///
+/// The macro accepts one or more host values or patterns and should be
 /// applied to async functions that accept a `&mut Context` parameter.
+/// A `capture = variable` option binds the part matched by the wildcard as a `String`,
+/// which is empty when the host matched a value without a wildcard.
+/// A trailing `respond = ...` or `else = ...` option, given as `code` or `(code, body)`,
+/// sends that response when the request is stopped.
 #[proc_macro_attribute]
-pub fn response_version(attr: TokenStream, item: TokenStream) -> TokenStream {
-    response_version_macro(attr, item, Position::Prologue)
+pub fn host(attr: TokenStream, item: TokenStream) -> TokenStream {
+    host_macro(attr, item, Position::Prologue)
 }
 
-/// Handles closed connection scenarios.
+/// Rejects requests whose host matches one of the specified values.
 ///
-/// This attribute macro configures the function to handle cases where the connection
-/// has been closed, providing appropriate handling for terminated or disconnected connections.
+/// This attribute macro ensures the decorated function only executes when the incoming request
+/// has a host that matches none of the specified values. Values follow the same
+/// case-insensitive, port-ignoring and wildcard matching as `host`.
 ///
 /// # Usage
 ///
@@ -1375,37 +2442,58 @@ pub fn response_version(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// use hyperlane::*;
 /// use hyperlane_macros::*;
 ///
-/// #[route("/closed")]
-/// struct ClosedTest;
+/// #[route("/reject_host")]
+/// struct RejectHost;
 ///
-/// impl ServerHook for ClosedTest {
+/// impl ServerHook for RejectHost {
 ///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
 ///         Self
 ///     }
 ///
-///     #[closed]
-///     async fn handle(self, stream: &mut Stream, _: &mut Context) -> Status { Status::Continue }
+///     #[prologue_macros(
+///         reject_host("filter.localhost"),
+///         response_body("host filter string literal")
+///     )]
+///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// }
 ///
-/// impl ClosedTest {
-///     #[closed]
-///     async fn closed_with_ref_self(&self, stream: &mut Stream, _: &mut Context) -> Status { Status::Continue }
+/// impl RejectHost {
+///     #[reject_host("filter.localhost")]
+///     async fn reject_host_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// }
 ///
-/// #[closed]
-/// async fn standalone_closed_handler(stream: &mut Stream, _: &mut Context) -> Status { Status::Continue }
+/// #[reject_host("filter.localhost")]
+/// async fn standalone_reject_host_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
-/// that accept a `&mut Context` parameter.
+/// Sending a response when the check fails:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::reject_host;
+///
+/// #[reject_host("blocked.example.com", respond = 403)]
+/// async fn responding_reject_host_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// The macro accepts one or more host values or patterns and should be applied to async
+/// functions that accept a `&mut Context` parameter.
+/// A trailing `respond = ...` or `else = ...` option, given as `code` or `(code, body)`,
+/// sends that response when the request is stopped.
 #[proc_macro_attribute]
-pub fn closed(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    closed_macro(item, Position::Prologue)
+pub fn reject_host(attr: TokenStream, item: TokenStream) -> TokenStream {
+    reject_host_macro(attr, item, Position::Prologue)
 }
 
-/// Filters requests based on a boolean condition.
+/// Restricts function execution to requests with a specific referer.
 ///
-/// The function continues execution only if the provided code block returns `true`.
+/// This attribute macro ensures the decorated function only executes when the incoming request
+/// has a referer header that matches one of the specified patterns. Requests with different or missing referer headers will be filtered out.
+/// A plain value must equal the header, `origin("https://*.example.com")` compares the scheme, host
+/// and port of the header, and `prefix("https://example.com/app")` additionally requires the path of
+/// the header to equal `/app` or continue it after a `/`. URLs are parsed before they are compared,
+/// so `prefix("https://example.com")` does not match `https://example.com.evil.net/`.
 ///
 /// # Usage
 ///
@@ -1413,37 +2501,78 @@ pub fn closed(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// use hyperlane::*;
 /// use hyperlane_macros::*;
 ///
-/// #[route("/is_unknown_method")]
-/// struct UnknownMethod;
+/// #[route("/referer")]
+/// struct Referer;
 ///
-/// impl ServerHook for UnknownMethod {
+/// impl ServerHook for Referer {
 ///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
 ///         Self
 ///     }
 ///
 ///     #[prologue_macros(
-///         filter(ctx.get_request().get_method().is_unknown()),
-///         response_body("is_unknown_method")
+///         referer("http://localhost"),
+///         response_body("referer string literal: http://localhost")
 ///     )]
 ///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// }
 ///
-/// impl UnknownMethod {
-///     #[filter(true)]
-///     async fn filter_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// impl Referer {
+///     #[referer("http://localhost")]
+///     async fn referer_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// }
 ///
-/// #[filter(true)]
-/// async fn standalone_filter_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// #[referer("http://localhost")]
+/// async fn standalone_referer_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// Sending a response when the check fails:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::referer;
+///
+/// #[referer("https://example.com", else = (403, "forbidden"))]
+/// async fn responding_referer_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// Matching by origin or path prefix:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::referer;
+///
+/// #[referer(origin("https://*.example.com"), prefix("https://cdn.example.net/assets/"), respond = 403)]
+/// async fn hotlink_referer_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// Several patterns are alternatives, and the request passes when any of them matches. Earlier
+/// versions required every value to match, which distinct values never could:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::referer;
+///
+/// #[referer("https://a.example.com/", "https://b.example.com/", else = 403)]
+/// async fn any_referer_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
+///
+/// The macro accepts one or more referer patterns and should be
+/// applied to async functions that accept a `&mut Context` parameter.
+/// A trailing `respond = ...` or `else = ...` option, given as `code` or `(code, body)`,
+/// sends that response when the request is stopped.
 #[proc_macro_attribute]
-pub fn filter(attr: TokenStream, item: TokenStream) -> TokenStream {
-    filter_macro(attr, item, Position::Prologue)
+pub fn referer(attr: TokenStream, item: TokenStream) -> TokenStream {
+    referer_macro(attr, item, Position::Prologue)
 }
 
-/// Rejects requests based on a boolean condition.
+/// Reject requests that have a specific referer header.
 ///
-/// The function continues execution only if the provided code block returns `false`.
+/// This attribute macro ensures the decorated function only executes when the incoming request
+/// does not have a referer header that matches one of the specified patterns. Requests with a matching referer header will be filtered out.
+/// Patterns follow the same exact, `origin(...)` and `prefix(...)` matching as `referer`.
 ///
 /// # Usage
 ///
@@ -1451,37 +2580,56 @@ pub fn filter(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// use hyperlane::*;
 /// use hyperlane_macros::*;
 ///
-/// #[response_middleware(2)]
-/// struct ResponseMiddleware2;
+/// #[route("/reject_referer")]
+/// struct RejectReferer;
 ///
-/// impl ServerHook for ResponseMiddleware2 {
+/// impl ServerHook for RejectReferer {
 ///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
 ///         Self
 ///     }
 ///
 ///     #[prologue_macros(
-///         reject(ctx.get_request().get_upgrade_type().is_ws())
+///         reject_referer("http://localhost"),
+///         response_body("referer filter string literal")
 ///     )]
 ///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// }
 ///
-/// impl ResponseMiddleware2 {
-///     #[reject(false)]
-///     async fn reject_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// impl RejectReferer {
+///     #[reject_referer("http://localhost")]
+///     async fn reject_referer_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// }
 ///
-/// #[reject(false)]
-/// async fn standalone_reject_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// #[reject_referer("http://localhost")]
+/// async fn standalone_reject_referer_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// Sending a response when the check fails:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::reject_referer;
+///
+/// #[reject_referer("https://spam.example.com", respond = 403)]
+/// async fn responding_reject_referer_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
+///
+/// The macro accepts one or more referer patterns to filter out and should be
+/// applied to async functions that accept a `&mut Context` parameter.
+/// A trailing `respond = ...` or `else = ...` option, given as `code` or `(code, body)`,
+/// sends that response when the request is stopped.
 #[proc_macro_attribute]
-pub fn reject(attr: TokenStream, item: TokenStream) -> TokenStream {
-    reject_macro(attr, item, Position::Prologue)
+pub fn reject_referer(attr: TokenStream, item: TokenStream) -> TokenStream {
+    reject_referer_macro(attr, item, Position::Prologue)
 }
 
-/// Restricts function execution to requests with a specific host.
+/// Restricts function execution to requests from specific origins.
 ///
-/// This attribute macro ensures the decorated function only executes when the incoming request
-/// has a host header that matches the specified value. Requests with different or missing host headers will be filtered out.
+/// This attribute macro ensures the decorated function only executes when the `Origin` header
+/// of the incoming request matches one of the specified origin patterns. Patterns have the form
+/// `scheme://host[:port]`, are compared case-insensitively using the default port of the scheme
+/// when none is given, and may start the host with a `*.` wildcard label.
 ///
 /// # Usage
 ///
@@ -1489,39 +2637,41 @@ pub fn reject(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// use hyperlane::*;
 /// use hyperlane_macros::*;
 ///
-/// #[route("/host")]
-/// struct Host;
+/// #[route("/origin")]
+/// struct Origin;
 ///
-/// impl ServerHook for Host {
+/// impl ServerHook for Origin {
 ///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
 ///         Self
 ///     }
 ///
-///     #[host("localhost")]
-///     #[prologue_macros(response_body("host string literal: localhost"), send)]
-///     async fn handle(self, stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+///     #[origin("https://example.com", "https://*.example.com")]
+///     #[response_body("origin checked")]
+///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// }
 ///
-/// impl Host {
-///     #[host("localhost")]
-///     async fn host_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// impl Origin {
+///     #[origin("http://localhost:8080", missing = allow)]
+///     async fn origin_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// }
 ///
-/// #[host("localhost")]
-/// async fn standalone_host_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// #[origin("https://app.example.com", missing = deny, respond = (403, "cross-origin request"))]
+/// async fn standalone_origin_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro accepts a string literal specifying the expected host value and should be
-/// applied to async functions that accept a `&mut Context` parameter.
+/// Requests without an `Origin` header are stopped unless `missing = allow` is given.
+/// A trailing `respond = ...` or `else = ...` option, given as `code` or `(code, body)`,
+/// sends that response when the request is stopped.
 #[proc_macro_attribute]
-pub fn host(attr: TokenStream, item: TokenStream) -> TokenStream {
-    host_macro(attr, item, Position::Prologue)
+pub fn origin(attr: TokenStream, item: TokenStream) -> TokenStream {
+    origin_macro(attr, item, Position::Prologue)
 }
 
-/// Reject requests that have no host header.
+/// Restricts function execution to clients within specific IP ranges.
 ///
-/// This attribute macro ensures the decorated function only executes when the incoming request
-/// has a host header present. Requests without a host header will be filtered out.
+/// This attribute macro ensures the decorated function only executes when the client address
+/// lies in one of the specified IPv4 or IPv6 ranges. Literal ranges use CIDR notation, a plain
+/// address matches only itself, and both are validated at compile time.
 ///
 /// # Usage
 ///
@@ -1529,41 +2679,48 @@ pub fn host(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// use hyperlane::*;
 /// use hyperlane_macros::*;
 ///
-/// #[route("/reject_host")]
-/// struct RejectHost;
+/// #[route("/allow_ip")]
+/// struct AllowIp;
 ///
-/// impl ServerHook for RejectHost {
+/// impl ServerHook for AllowIp {
 ///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
 ///         Self
 ///     }
 ///
-///     #[prologue_macros(
-///         reject_host("filter.localhost"),
-///         response_body("host filter string literal")
-///     )]
-///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+///     #[allow_ip("10.0.0.0/8", "192.168.1.0/24", "::1")]
+///     #[response_body("internal")]
+///     async fn handle(self, stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// }
 ///
-/// impl RejectHost {
-///     #[reject_host("filter.localhost")]
-///     async fn reject_host_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// impl AllowIp {
+///     #[allow_ip("127.0.0.1", trusted_proxies("10.0.0.0/8"), respond = (403, "forbidden"))]
+///     async fn allow_ip_with_ref_self(&self, stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// }
 ///
-/// #[reject_host("filter.localhost")]
-/// async fn standalone_reject_host_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// static ADMIN_RANGES: &[&str] = &["172.16.0.0/12", "fd00::/8"];
+///
+/// #[allow_ip(ranges = ADMIN_RANGES, else = 403)]
+/// async fn standalone_allow_ip_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro takes no parameters and should be applied directly to async functions
-/// that accept a `&mut Context` parameter.
+/// The client address is the peer address of the connection. When the peer lies in one of the
+/// `trusted_proxies(...)` ranges, the rightmost `X-Forwarded-For` entry that is not a trusted proxy
+/// is used instead, falling back to `X-Real-IP`. A `ranges = ...` option adds ranges from any
+/// iterable of string values. It is evaluated and parsed on every request, and entries that are
+/// not valid ranges are skipped.
+/// Requests whose client address cannot be determined are stopped.
+/// A trailing `respond = ...` or `else = ...` option, given as `code` or `(code, body)`,
+/// sends that response when the request is stopped.
 #[proc_macro_attribute]
-pub fn reject_host(attr: TokenStream, item: TokenStream) -> TokenStream {
-    reject_host_macro(attr, item, Position::Prologue)
+pub fn allow_ip(attr: TokenStream, item: TokenStream) -> TokenStream {
+    allow_ip_macro(attr, item, Position::Prologue)
 }
 
-/// Restricts function execution to requests with a specific referer.
+/// Rejects clients within specific IP ranges.
 ///
-/// This attribute macro ensures the decorated function only executes when the incoming request
-/// has a referer header that matches the specified value. Requests with different or missing referer headers will be filtered out.
+/// This attribute macro ensures the decorated function only executes when the client address
+/// lies in none of the specified IPv4 or IPv6 ranges. Ranges and options are the same as
+/// for `allow_ip`.
 ///
 /// # Usage
 ///
@@ -1571,41 +2728,40 @@ pub fn reject_host(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// use hyperlane::*;
 /// use hyperlane_macros::*;
 ///
-/// #[route("/referer")]
-/// struct Referer;
+/// #[route("/deny_ip")]
+/// struct DenyIp;
 ///
-/// impl ServerHook for Referer {
+/// impl ServerHook for DenyIp {
 ///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
 ///         Self
 ///     }
 ///
-///     #[prologue_macros(
-///         referer("http://localhost"),
-///         response_body("referer string literal: http://localhost")
-///     )]
-///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
-/// }
-///
-/// impl Referer {
-///     #[referer("http://localhost")]
-///     async fn referer_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+///     #[deny_ip("203.0.113.0/24", "2001:db8::/32", respond = 403)]
+///     #[response_body("welcome")]
+///     async fn handle(self, stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// }
 ///
-/// #[referer("http://localhost")]
-/// async fn standalone_referer_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// #[deny_ip("198.51.100.7", trusted_proxies("127.0.0.1"))]
+/// async fn standalone_deny_ip_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro accepts a string literal specifying the expected referer value and should be
-/// applied to async functions that accept a `&mut Context` parameter.
+/// Requests whose client address cannot be determined are stopped.
+/// A trailing `respond = ...` or `else = ...` option, given as `code` or `(code, body)`,
+/// sends that response when the request is stopped.
 #[proc_macro_attribute]
-pub fn referer(attr: TokenStream, item: TokenStream) -> TokenStream {
-    referer_macro(attr, item, Position::Prologue)
+pub fn deny_ip(attr: TokenStream, item: TokenStream) -> TokenStream {
+    deny_ip_macro(attr, item, Position::Prologue)
 }
 
-/// Reject requests that have a specific referer header.
+/// Limits the request rate of a handler or middleware.
 ///
-/// This attribute macro ensures the decorated function only executes when the incoming request
-/// does not have a referer header that matches the specified value. Requests with the matching referer header will be filtered out.
+/// This attribute macro keeps an in-process token bucket per key. Each bucket holds the given
+/// number of requests and refills over the `per` period, given as a number with a `ms`, `s`,
+/// `m`, `h` or `d` unit. Requests are keyed by the client address, which `key = client_ip` states
+/// explicitly, or by any other `key = expr` expression such as a header or route parameter.
+/// When the limit is exceeded the request is answered with `429 Too Many Requests` and a
+/// `Retry-After` header, and every response carries `RateLimit-Limit`, `RateLimit-Remaining`
+/// and `RateLimit-Reset` headers.
 ///
 /// # Usage
 ///
@@ -1613,35 +2769,41 @@ pub fn referer(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// use hyperlane::*;
 /// use hyperlane_macros::*;
 ///
-/// #[route("/reject_referer")]
-/// struct RejectReferer;
+/// #[route("/rate_limit")]
+/// struct RateLimit;
 ///
-/// impl ServerHook for RejectReferer {
+/// impl ServerHook for RateLimit {
 ///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
 ///         Self
 ///     }
 ///
-///     #[prologue_macros(
-///         reject_referer("http://localhost"),
-///         response_body("referer filter string literal")
-///     )]
-///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+///     #[rate_limit(100, per = "1m", key = client_ip)]
+///     #[response_body("limited")]
+///     async fn handle(self, stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// }
 ///
-/// impl RejectReferer {
-///     #[reject_referer("http://localhost")]
-///     async fn reject_referer_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// impl RateLimit {
+///     #[rate_limit(5, per = "1s", key = ctx.get_request().try_get_header_back("x-api-key").unwrap_or_default())]
+///     async fn rate_limit_with_ref_self(&self, stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// }
 ///
-/// #[reject_referer("http://localhost")]
-/// async fn standalone_reject_referer_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// #[rate_limit(10, per = "1h", max_keys = 1_000, respond = (429, "slow down"))]
+/// async fn standalone_rate_limit_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+///
+/// #[rate_limit(60, per = "1m", trusted_proxies("10.0.0.0/8", "::1"))]
+/// async fn proxied_rate_limit_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// The macro accepts a string literal specifying the referer value to filter out and should be
-/// applied to async functions that accept a `&mut Context` parameter.
+/// The client address is determined as `allow_ip` does: it is the peer address unless the peer
+/// lies in one of the `trusted_proxies(...)` ranges, in which case the forwarded address is used.
+/// Requests whose client address cannot be determined are not limited, since they have no key to
+/// tell them apart; a `key = expr` option is always used as given. Every expansion owns its own
+/// store, so each route is limited separately. The store holds at most `max_keys` keys, 10000 by
+/// default, and evicts the least recently used key when a new key arrives at a full store. A `respond = ...` or `else = ...` option, given as `code` or
+/// `(code, body)`, replaces the default `429` response.
 #[proc_macro_attribute]
-pub fn reject_referer(attr: TokenStream, item: TokenStream) -> TokenStream {
-    reject_referer_macro(attr, item, Position::Prologue)
+pub fn rate_limit(attr: TokenStream, item: TokenStream) -> TokenStream {
+    rate_limit_macro(attr, item, Position::Prologue)
 }
 
 /// Executes multiple specified functions before the main handler function.
@@ -1956,68 +3118,327 @@ pub fn request_body_json_result(attr: TokenStream, item: TokenStream) -> TokenSt
 ///     age: u32,
 /// }
 ///
-/// #[route("/request_body_json")]
-/// struct RequestBodyJson;
+/// #[route("/request_body_json")]
+/// struct RequestBodyJson;
+///
+/// impl ServerHook for RequestBodyJson {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[response_body(&format!("request data: {request_data_result:?}"))]
+///     #[request_body_json(request_data_result: TestData)]
+///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// impl RequestBodyJson {
+///     #[request_body_json(request_data_result: TestData)]
+///     async fn request_body_json_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// #[request_body_json(request_data_result: TestData)]
+/// async fn standalone_request_body_json_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// # Multi-Parameter Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Clone, Debug, Deserialize, Serialize)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// #[derive(Clone, Debug, Deserialize, Serialize)]
+/// struct Config {
+///     debug: bool,
+/// }
+///
+/// #[route("/request_body_json")]
+/// struct TestData;
+///
+/// impl ServerHook for TestData {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[response_body(&format!("user: {user:?}, config: {config:?}"))]
+///     #[request_body_json(user: User, config: Config)]
+///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+/// ```
+///
+/// The macro accepts one or more `variable_name: Type` pairs separated by commas.
+/// Each variable will be available in the function scope as a `Result<Type, serde_json::Error>`.
+///
+/// # Panics
+///
+/// This macro will panic if the request body does not exist or JSON parsing fails.
+#[proc_macro_attribute]
+pub fn request_body_json(attr: TokenStream, item: TokenStream) -> TokenStream {
+    request_body_json_macro(attr, item, Position::Prologue)
+}
+
+/// Rejects requests whose body exceeds a maximum size in bytes.
+///
+/// This attribute macro checks both the declared `Content-Length` header and the length of the
+/// body that was read before the function body runs, and answers oversized requests with
+/// `413 Payload Too Large`.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct UploadData {
+///     name: String,
+/// }
+///
+/// #[route("/max_body_size")]
+/// struct MaxBodySize;
+///
+/// impl ServerHook for MaxBodySize {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[request_body_json_result(upload: UploadData)]
+///     #[max_body_size(1_048_576)]
+///     async fn handle(self, stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// #[request_body_json_result(upload: UploadData)]
+/// #[max_body_size(64 * 1024, respond = (413, "upload too large"))]
+/// async fn standalone_max_body_size_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// Stacked attributes run from the one closest to the function outwards, so `max_body_size`
+/// must be placed below `request_body`, `request_body_json` and `request_body_json_result`,
+/// or first in `prologue_macros`, to stop oversized payloads before they are parsed. Placing
+/// it above one of them is a compile error:
+///
+/// ```rust,compile_fail
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// #[derive(Debug, serde::Deserialize)]
+/// struct UploadData {
+///     name: String,
+/// }
+///
+/// #[max_body_size(1_048_576)]
+/// #[request_body_json_result(upload: UploadData)]
+/// async fn misplaced_max_body_size_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// A trailing `respond = ...` or `else = ...`
+/// option, given as `code` or `(code, body)`, replaces the default response.
+#[proc_macro_attribute]
+pub fn max_body_size(attr: TokenStream, item: TokenStream) -> TokenStream {
+    max_body_size_macro(attr, item, Position::Prologue)
+}
+
+/// Restricts function execution to requests carrying specific headers.
+///
+/// This attribute macro ensures the decorated function only executes when every specified
+/// header is present. Header names are matched case-insensitively.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// #[route("/require_header")]
+/// struct RequireHeader;
+///
+/// impl ServerHook for RequireHeader {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[require_header("X-Api-Key")]
+///     #[response_body("authorized")]
+///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// #[require_header("X-Api-Key", "Authorization", respond = (401, "missing credentials"))]
+/// async fn standalone_require_header_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// A trailing `respond = ...` or `else = ...` option, given as `code` or `(code, body)`,
+/// sends that response when the request is stopped.
+#[proc_macro_attribute]
+pub fn require_header(attr: TokenStream, item: TokenStream) -> TokenStream {
+    require_header_macro(attr, item, Position::Prologue)
+}
+
+/// Restricts function execution to requests whose headers equal specific values.
+///
+/// This attribute macro ensures the decorated function only executes when every specified
+/// header is present and equals its value. Header names are matched case-insensitively.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// const X_TENANT: &str = "x-tenant";
+///
+/// #[route("/header_eq")]
+/// struct HeaderEq;
+///
+/// impl ServerHook for HeaderEq {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[header_eq(X_TENANT => "acme")]
+///     #[response_body("acme tenant")]
+///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// #[header_eq(X_TENANT => "acme", "X-Region" => "eu", else = 403)]
+/// async fn standalone_header_eq_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// A trailing `respond = ...` or `else = ...` option, given as `code` or `(code, body)`,
+/// sends that response when the request is stopped.
+#[proc_macro_attribute]
+pub fn header_eq(attr: TokenStream, item: TokenStream) -> TokenStream {
+    header_eq_macro(attr, item, Position::Prologue)
+}
+
+/// Restricts function execution to requests whose headers match specific patterns.
+///
+/// This attribute macro ensures the decorated function only executes when every specified
+/// header is present and accepted by its matcher. A matcher is any expression with an
+/// `is_match(&str) -> bool` method, such as a `regex::Regex`. Header names are matched
+/// case-insensitively.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use regex::Regex;
+/// use std::sync::LazyLock;
+///
+/// static BROWSER: LazyLock<Regex> = LazyLock::new(|| Regex::new("Mozilla/\\d").unwrap());
+///
+/// #[route("/header_matches")]
+/// struct HeaderMatches;
+///
+/// impl ServerHook for HeaderMatches {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[header_matches(USER_AGENT => BROWSER)]
+///     #[response_body("browser")]
+///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// #[header_matches(USER_AGENT => BROWSER, respond = (400, "browsers only"))]
+/// async fn standalone_header_matches_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// A trailing `respond = ...` or `else = ...` option, given as `code` or `(code, body)`,
+/// sends that response when the request is stopped.
+#[proc_macro_attribute]
+pub fn header_matches(attr: TokenStream, item: TokenStream) -> TokenStream {
+    header_matches_macro(attr, item, Position::Prologue)
+}
+
+/// Restricts function execution to requests carrying specific query parameters.
+///
+/// This attribute macro ensures the decorated function only executes when every specified
+/// query parameter is present.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// #[route("/require_query")]
+/// struct RequireQuery;
 ///
-/// impl ServerHook for RequestBodyJson {
+/// impl ServerHook for RequireQuery {
 ///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
 ///         Self
 ///     }
 ///
-///     #[response_body(&format!("request data: {request_data_result:?}"))]
-///     #[request_body_json(request_data_result: TestData)]
+///     #[require_query("page", "size")]
+///     #[response_body("paged")]
 ///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// }
 ///
-/// impl RequestBodyJson {
-///     #[request_body_json(request_data_result: TestData)]
-///     async fn request_body_json_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
-/// }
-///
-/// #[request_body_json(request_data_result: TestData)]
-/// async fn standalone_request_body_json_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// #[require_query("token", respond = (400, "missing token"))]
+/// async fn standalone_require_query_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
 /// ```
 ///
-/// # Multi-Parameter Usage
+/// A trailing `respond = ...` or `else = ...` option, given as `code` or `(code, body)`,
+/// sends that response when the request is stopped.
+#[proc_macro_attribute]
+pub fn require_query(attr: TokenStream, item: TokenStream) -> TokenStream {
+    require_query_macro(attr, item, Position::Prologue)
+}
+
+/// Restricts function execution to requests whose query parameters equal specific values.
+///
+/// This attribute macro ensures the decorated function only executes when every specified
+/// query parameter is present and equals its value.
+///
+/// # Usage
 ///
 /// ```rust
 /// use hyperlane::*;
 /// use hyperlane_macros::*;
-/// use serde::{Deserialize, Serialize};
 ///
-/// #[derive(Clone, Debug, Deserialize, Serialize)]
-/// struct User {
-///     name: String,
-/// }
+/// #[query_eq("format" => "json", else = (400, "unsupported format"))]
+/// async fn standalone_query_eq_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
 ///
-/// #[derive(Clone, Debug, Deserialize, Serialize)]
-/// struct Config {
-///     debug: bool,
-/// }
+/// A trailing `respond = ...` or `else = ...` option, given as `code` or `(code, body)`,
+/// sends that response when the request is stopped.
+#[proc_macro_attribute]
+pub fn query_eq(attr: TokenStream, item: TokenStream) -> TokenStream {
+    query_eq_macro(attr, item, Position::Prologue)
+}
+
+/// Restricts function execution to requests whose query parameters match specific patterns.
 ///
-/// #[route("/request_body_json")]
-/// struct TestData;
+/// This attribute macro ensures the decorated function only executes when every specified
+/// query parameter is present and accepted by its matcher. A matcher is any expression with
+/// an `is_match(&str) -> bool` method, such as a `regex::Regex`.
 ///
-/// impl ServerHook for TestData {
-///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
-///         Self
-///     }
+/// # Usage
 ///
-///     #[response_body(&format!("user: {user:?}, config: {config:?}"))]
-///     #[request_body_json(user: User, config: Config)]
-///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
-/// }
-/// ```
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use regex::Regex;
+/// use std::sync::LazyLock;
 ///
-/// The macro accepts one or more `variable_name: Type` pairs separated by commas.
-/// Each variable will be available in the function scope as a `Result<Type, serde_json::Error>`.
+/// static NUMBER: LazyLock<Regex> = LazyLock::new(|| Regex::new("^\\d+$").unwrap());
 ///
-/// # Panics
+/// #[query_matches("page" => NUMBER, respond = 400)]
+/// async fn standalone_query_matches_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
 ///
-/// This macro will panic if the request body does not exist or JSON parsing fails.
+/// A trailing `respond = ...` or `else = ...` option, given as `code` or `(code, body)`,
+/// sends that response when the request is stopped.
 #[proc_macro_attribute]
-pub fn request_body_json(attr: TokenStream, item: TokenStream) -> TokenStream {
-    request_body_json_macro(attr, item, Position::Prologue)
+pub fn query_matches(attr: TokenStream, item: TokenStream) -> TokenStream {
+    query_matches_macro(attr, item, Position::Prologue)
 }
 
 /// Extracts a specific attribute value into a variable wrapped in Option type.
@@ -3109,6 +4530,210 @@ pub fn request_cookies(attr: TokenStream, item: TokenStream) -> TokenStream {
     request_cookies_macro(attr, item, Position::Prologue)
 }
 
+/// Declares a cookie key whose cookies are signed.
+///
+/// This function-like macro expands to a lazily initialized static that `request_signed_cookie`
+/// and `response_signed_cookie` name to read and write cookies. Signed values stay readable
+/// by the client behind an HMAC-SHA256 tag over the cookie name and value. Signing is done
+/// by the `cookie` crate, which must be a dependency with its `signed` feature enabled.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane_macros::*;
+///
+/// signed_cookie_key!(SESSION_KEY = b"0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef");
+/// signed_cookie_key!(pub(crate) TOKEN_KEY = std::env::var("TOKEN_KEY").unwrap_or_default());
+/// ```
+///
+/// The arguments are the key name, optionally preceded by a visibility, and the key, any
+/// expression implementing `AsRef<[u8]>`. The key must be at least 64 bytes. A shorter
+/// literal is a compile error, and any other key is checked when it is built, at startup
+/// once it is listed in `#[hyperlane(cookie_key = KEY)]`.
+///
+/// ```rust,compile_fail
+/// use hyperlane_macros::*;
+///
+/// signed_cookie_key!(SESSION_KEY = "secret");
+/// ```
+#[proc_macro]
+pub fn signed_cookie_key(input: TokenStream) -> TokenStream {
+    cookie_key_macro(input, CookieKeyMode::Signed)
+}
+
+/// Declares a cookie key whose cookies are encrypted.
+///
+/// This function-like macro expands to a lazily initialized static that `request_signed_cookie`
+/// and `response_signed_cookie` name to read and write cookies. Encrypted values are sealed
+/// with AES-256-GCM under a random 96-bit nonce, with the cookie name as associated data, so
+/// the client can neither read nor change them. Encryption is done by the `cookie` crate,
+/// which must be a dependency with its `private` feature enabled.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane_macros::*;
+///
+/// encrypted_cookie_key!(SECRET_KEY = b"fedcba9876543210fedcba9876543210fedcba9876543210fedcba9876543210");
+/// encrypted_cookie_key!(pub(crate) VAULT_KEY = std::env::var("VAULT_KEY").unwrap_or_default());
+/// ```
+///
+/// The arguments are the same as for `signed_cookie_key`.
+#[proc_macro]
+pub fn encrypted_cookie_key(input: TokenStream) -> TokenStream {
+    cookie_key_macro(input, CookieKeyMode::Encrypted)
+}
+
+/// Extracts signed or encrypted cookie values into variables wrapped in Option type.
+///
+/// The first argument names a key declared with `signed_cookie_key!` or
+/// `encrypted_cookie_key!`, so a missing key is a compile error.
+/// Cookies that are missing, were not produced with the key, or were tampered with
+/// are all extracted as `None`.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::request_signed_cookie;
+///
+/// signed_cookie_key!(SESSION_KEY = b"0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef");
+///
+/// #[route("/signed_cookie")]
+/// struct SignedCookie;
+///
+/// impl ServerHook for SignedCookie {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[response_body(&format!("Session: {session_option:?}, user: {user_option:?}"))]
+///     #[request_signed_cookie(SESSION_KEY, "session" => session_option, "user" => user_option)]
+///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// impl SignedCookie {
+///     #[request_signed_cookie(SESSION_KEY, "session" => session_option)]
+///     async fn request_signed_cookie_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// #[request_signed_cookie(SESSION_KEY, "session" => session_option)]
+/// async fn standalone_request_signed_cookie_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// Reading back the cookies written by `response_signed_cookie`:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::{request_signed_cookie, response_signed_cookie};
+///
+/// signed_cookie_key!(SESSION_KEY = b"0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef");
+/// encrypted_cookie_key!(SECRET_KEY = b"fedcba9876543210fedcba9876543210fedcba9876543210fedcba9876543210");
+///
+/// #[response_signed_cookie(SESSION_KEY, "session" => "abc")]
+/// #[response_signed_cookie(SECRET_KEY, "secret" => "hidden")]
+/// async fn write_cookies(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+///
+/// #[request_signed_cookie(SESSION_KEY, "session" => session)]
+/// #[request_signed_cookie(SECRET_KEY, "secret" => secret)]
+/// async fn read_cookies(_: &mut Stream, ctx: &mut Context) -> Status {
+///     ctx.set_attribute("cookies", (session, secret));
+///     Status::Continue
+/// }
+///
+/// async fn read(stream: &mut Stream, cookie: &str) -> (Option<String>, Option<String>) {
+///     let mut ctx: Context = Context::default();
+///     ctx.get_mut_request().get_mut_headers().insert(
+///         COOKIE.to_owned(),
+///         std::collections::VecDeque::from([cookie.to_owned()]),
+///     );
+///     read_cookies(stream, &mut ctx).await;
+///     ctx.try_get_attribute("cookies").unwrap()
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let listener: tokio::net::TcpListener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+/// let tcp_stream: tokio::net::TcpStream =
+///     tokio::net::TcpStream::connect(listener.local_addr().unwrap()).await.unwrap();
+/// let mut stream: Stream = Stream::new(tcp_stream, RequestConfig::default(), false);
+/// let mut ctx: Context = Context::default();
+/// write_cookies(&mut stream, &mut ctx).await;
+/// let set_cookies: Vec<String> = ctx
+///     .get_response()
+///     .get_headers()
+///     .get(SET_COOKIE)
+///     .unwrap()
+///     .iter()
+///     .map(|set_cookie: &String| set_cookie.split(';').next().unwrap().to_owned())
+///     .collect();
+/// let find = |name: &str| set_cookies.iter().find(|set_cookie| set_cookie.starts_with(name)).unwrap();
+/// let signed: &str = find("session=");
+/// let encrypted: &str = find("secret=");
+/// assert!(signed.ends_with("abc"));
+/// assert!(!encrypted.contains("hidden"));
+/// let cookies: String = format!("{signed}; {encrypted}");
+/// assert_eq!(
+///     read(&mut stream, &cookies).await,
+///     (Some("abc".to_owned()), Some("hidden".to_owned()))
+/// );
+/// let tampered: String = format!("{}; {}x", signed.replace("abc", "abd"), encrypted);
+/// assert_eq!(read(&mut stream, &tampered).await, (None, None));
+/// assert_eq!(read(&mut stream, "session=abc; secret=hidden").await, (None, None));
+/// let swapped: String = format!("session={}; secret={}", &encrypted[7..], &signed[8..]);
+/// assert_eq!(read(&mut stream, &swapped).await, (None, None));
+/// # });
+/// ```
+///
+/// The variables will be available as `Option<CookieValue>`.
+#[proc_macro_attribute]
+pub fn request_signed_cookie(attr: TokenStream, item: TokenStream) -> TokenStream {
+    request_signed_cookie_macro(attr, item, Position::Prologue)
+}
+
+/// Signs or encrypts cookie values and adds them to the response as `Set-Cookie` headers.
+///
+/// The first argument names a key declared with `signed_cookie_key!` or
+/// `encrypted_cookie_key!`, so a missing key is a compile error.
+/// Signed values stay readable by the client, encrypted values do not.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::response_signed_cookie;
+///
+/// encrypted_cookie_key!(SESSION_KEY = b"fedcba9876543210fedcba9876543210fedcba9876543210fedcba9876543210");
+///
+/// #[route("/response_signed_cookie")]
+/// struct ResponseSignedCookie;
+///
+/// impl ServerHook for ResponseSignedCookie {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[response_signed_cookie(SESSION_KEY, "session" => "abc", "user" => "hyperlane")]
+///     async fn handle(self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// impl ResponseSignedCookie {
+///     #[response_signed_cookie(SESSION_KEY, "session" => "abc")]
+///     async fn response_signed_cookie_with_ref_self(&self, _: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// #[response_signed_cookie(SESSION_KEY, "session" => "abc")]
+/// async fn standalone_response_signed_cookie_handler(_: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// Values can be any expression implementing `AsRef<str>`.
+#[proc_macro_attribute]
+pub fn response_signed_cookie(attr: TokenStream, item: TokenStream) -> TokenStream {
+    response_signed_cookie_macro(attr, item, Position::Prologue)
+}
+
 /// Extracts the HTTP request version into a variable.
 ///
 /// This attribute macro retrieves the HTTP version from the request and makes it
@@ -3239,8 +4864,27 @@ pub fn request_path(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// Building cookie keys for the signed cookie macros at startup:
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+/// use hyperlane_macros::hyperlane;
+///
+/// signed_cookie_key!(SESSION_KEY = b"0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef");
+/// encrypted_cookie_key!(SECRET_KEY = b"fedcba9876543210fedcba9876543210fedcba9876543210fedcba9876543210");
+///
+/// #[hyperlane(server: Server, cookie_key = SESSION_KEY, cookie_key = SECRET_KEY)]
+/// async fn create_cookie_server() -> Server {
+///     server
+/// }
+/// ```
+///
 /// The macro accepts a `variable_name: Type` pair.
 /// The variable will be available as an instance of the specified type in the function scope.
+/// A `cookie_key = KEY` entry builds a key declared with `signed_cookie_key!` or
+/// `encrypted_cookie_key!` before the function body runs, so an invalid key fails at startup
+/// instead of on the first request using it.
 #[proc_macro_attribute]
 pub fn hyperlane(attr: TokenStream, item: TokenStream) -> TokenStream {
     hyperlane_macro(attr, item)
@@ -3615,6 +5259,109 @@ pub fn send(attr: TokenStream, item: TokenStream) -> TokenStream {
     send_macro(attr, item, Position::Epilogue)
 }
 
+/// Sets the response version, status code, headers and body and sends it in one step.
+///
+/// This attribute macro replaces the usual sequence of `response_version`, `response_status_code`,
+/// `response_header`, `response_body` and `send`, expanding to the same calls in that order
+/// after the function body. Every option is optional and may appear at most once.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// #[route("/respond")]
+/// struct Respond;
+///
+/// impl ServerHook for Respond {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[respond(200, headers(CONTENT_TYPE => TEXT_PLAIN, SERVER => HYPERLANE), body = "respond", send)]
+///     async fn handle(self, stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// impl Respond {
+///     #[respond(version = HttpVersion::Http1_1, status = 404, body = "not found", try_send)]
+///     async fn respond_with_ref_self(&self, stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// #[respond(201, body = format!("created {}", 1))]
+/// async fn standalone_respond_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+///
+/// #[respond(version = "HTTP/1.0", status = 204, try_send)]
+/// async fn literal_version_respond_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+///
+/// fn code_for(missing: bool) -> usize {
+///     if missing { 404 } else { 200 }
+/// }
+///
+/// #[respond(code_for(true), try_send)]
+/// async fn computed_status_respond_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// The status code is given positionally or as `status = code`, and literal codes are
+/// validated like `response_status_code`. Versions accept the same `HttpVersion` expressions
+/// and string literals as `response_version`. Headers are set with replace semantics.
+/// The response is only sent when `send` (panics on failure) or `try_send` is given.
+#[proc_macro_attribute]
+pub fn respond(attr: TokenStream, item: TokenStream) -> TokenStream {
+    respond_macro(attr, item, Position::Epilogue)
+}
+
+/// Sends a redirect response and stops the handler.
+///
+/// This attribute macro sets the status code and the `Location` header, clears the body,
+/// sends the response and returns `Status::Reject` so that no later hook sends again.
+/// Both the status code and the location accept expressions.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// #[route("/redirect/{id}")]
+/// struct Redirect;
+///
+/// impl ServerHook for Redirect {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[redirect(301 => format!("/items/{id}"))]
+///     #[route_param("id" => id)]
+///     async fn handle(self, stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// impl Redirect {
+///     #[redirect(302 => "/login")]
+///     async fn redirect_with_ref_self(&self, stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// }
+///
+/// #[redirect(307 => "/login")]
+/// async fn standalone_redirect_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// Literal status codes must be one of 301, 302, 303, 307 or 308:
+///
+/// ```rust,compile_fail
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// #[redirect(200 => "/login")]
+/// async fn invalid_redirect_handler(stream: &mut Stream, ctx: &mut Context) -> Status { Status::Continue }
+/// ```
+///
+/// The macro accepts a `status_code => location` pair and should be applied to async
+/// functions that accept both `&mut Stream` and `&mut Context` parameters.
+#[proc_macro_attribute]
+pub fn redirect(attr: TokenStream, item: TokenStream) -> TokenStream {
+    redirect_macro(attr, item, Position::Prologue)
+}
+
 /// Tries to flush the response stream after function execution.
 ///
 /// This attribute macro ensures that the response stream is tried to be flushed to guarantee immediate
@@ -3654,6 +5401,113 @@ pub fn try_flush(_attr: TokenStream, item: TokenStream) -> TokenStream {
     try_flush_macro(item, Position::Prologue)
 }
 
+/// Starts a Server-Sent Events response before the function body runs.
+///
+/// This attribute macro sets `Content-Type: text/event-stream`, `Cache-Control: no-cache` and
+/// `Connection: keep-alive` on the response, then sends and flushes the response head so the
+/// body can stream events with `sse_send!`.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// #[route("/events")]
+/// struct Events;
+///
+/// impl ServerHook for Events {
+///     async fn new(_: &mut Stream, _: &mut Context) -> Self {
+///         Self
+///     }
+///
+///     #[sse]
+///     async fn handle(self, stream: &mut Stream, ctx: &mut Context) -> Status {
+///         for id in 0..3 {
+///             if sse_send!(stream, event = "tick", id = id, data = id).is_err() {
+///                 return Status::Reject;
+///             }
+///         }
+///         Status::Continue
+///     }
+/// }
+///
+/// #[sse]
+/// async fn standalone_sse_handler(stream: &mut Stream, ctx: &mut Context) -> Status {
+///     Status::Continue
+/// }
+/// ```
+///
+/// The function returns `Status::Reject` when the response head cannot be sent. The macro takes
+/// no parameters and should be applied directly to async functions that accept `&mut Stream`
+/// and `&mut Context` parameters.
+#[proc_macro_attribute]
+pub fn sse(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    sse_macro(item, Position::Prologue)
+}
+
+/// Sends one Server-Sent Events frame.
+///
+/// This function-like macro formats the given fields as an event, sends it and flushes the
+/// stream, evaluating to the `Result<(), ResponseError>` of doing so.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// async fn send_update(stream: &mut Stream, ctx: &mut Context) -> Result<(), ResponseError> {
+///     let update: serde_json::Value = serde_json::json!({ "price": 42 });
+///     sse_send!(stream, event = "update", id = 7, data = update)?;
+///     sse_send!(stream, data = "first line\nsecond line")?;
+///     sse_send!(stream, retry = 5000, comment = "reconnect slowly")?;
+///     Ok(())
+/// }
+/// ```
+///
+/// The first argument is the stream, followed by any of `comment`, `event`, `id`, `retry` and
+/// `data`, which are written in that order. Values are converted with `ToString`, and `retry`
+/// takes the reconnection time in milliseconds as a `u64`. Multi-line `data` and `comment`
+/// values are split into one line per value line, while line breaks are removed from `event`
+/// and `id`.
+#[proc_macro]
+pub fn sse_send(input: TokenStream) -> TokenStream {
+    sse_send_macro(input)
+}
+
+/// Awaits a future while keeping a Server-Sent Events stream alive.
+///
+/// This function-like macro sends a `: keep-alive` comment frame whenever the future has not
+/// completed within the keep-alive interval, so idle streams are not dropped by proxies.
+///
+/// # Usage
+///
+/// ```rust
+/// use hyperlane::*;
+/// use hyperlane_macros::*;
+///
+/// #[sse]
+/// async fn price_stream(stream: &mut Stream, ctx: &mut Context) -> Status {
+///     let (sender, mut receiver) = tokio::sync::mpsc::channel::<String>(16);
+///     drop(sender);
+///     while let Ok(Some(price)) = sse_wait!(stream, receiver.recv(), keep_alive = "15s") {
+///         if sse_send!(stream, event = "price", data = price).is_err() {
+///             break;
+///         }
+///     }
+///     Status::Continue
+/// }
+/// ```
+///
+/// The arguments are the stream, the future and an optional `keep_alive` interval, `"15s"` when
+/// omitted. The macro evaluates to `Ok` with the output of the future, or to the `ResponseError`
+/// of a failed keep-alive once the client has gone away.
+#[proc_macro]
+pub fn sse_wait(input: TokenStream) -> TokenStream {
+    sse_wait_macro(input)
+}
+
 /// Flushes the response stream after function execution.
 ///
 /// This attribute macro ensures that the response stream is flushed to guarantee immediate
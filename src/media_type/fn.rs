@@ -0,0 +1,247 @@
+use super::*;
+
+/// Validates a literal media type.
+///
+/// The media type must have the form `type/subtype`. A `*` is allowed as the whole type,
+/// the whole subtype or the start of a structured syntax suffix such as `*+json`.
+///
+/// # Arguments
+///
+/// - `&Expr` - The media type expression.
+///
+/// # Returns
+///
+/// - `syn::Result<()>` - An error spanned at the media type when a literal media type is malformed.
+pub(crate) fn validate_media_type(media_type: &Expr) -> syn::Result<()> {
+    let Expr::Lit(ExprLit {
+        lit: Lit::Str(value),
+        ..
+    }) = media_type
+    else {
+        return Ok(());
+    };
+    let value: String = value.value();
+    let is_valid: bool = value.split_once('/').is_some_and(|(main, sub)| {
+        let is_token = |part: &str| {
+            !part.is_empty()
+                && part
+                    .bytes()
+                    .all(|byte: u8| byte.is_ascii_alphanumeric() || b"!#$&-^_.+".contains(&byte))
+        };
+        if main == "*" {
+            sub == "*"
+        } else {
+            is_token(main) && (sub == "*" || is_token(sub.strip_prefix('*').unwrap_or(sub)))
+        }
+    });
+    if !is_valid {
+        return Err(syn::Error::new_spanned(
+            media_type,
+            "expected a media type of the form `type/subtype`, such as \"application/json\" or \"application/*+json\"",
+        ));
+    }
+    Ok(())
+}
+
+/// Validates that a literal media type contains no wildcard.
+///
+/// The media types of `produces` are set as the response `Content-Type`, so unlike the
+/// ranges accepted by `consumes` they must name a concrete type and subtype.
+///
+/// # Arguments
+///
+/// - `&Expr` - The media type expression.
+///
+/// # Returns
+///
+/// - `syn::Result<()>` - An error spanned at the media type when a literal media type contains `*`.
+fn validate_concrete_media_type(media_type: &Expr) -> syn::Result<()> {
+    let Expr::Lit(ExprLit {
+        lit: Lit::Str(value),
+        ..
+    }) = media_type
+    else {
+        return Ok(());
+    };
+    if value.value().contains('*') {
+        return Err(syn::Error::new_spanned(
+            media_type,
+            "expected a concrete media type without `*`, such as \"application/json\"",
+        ));
+    }
+    Ok(())
+}
+
+/// Generates the runtime helper matching a media type against a media range.
+///
+/// `media_range_specificity` returns `None` when the media type is outside the range, and
+/// otherwise how specific the range is: `0` for `*/*`, `1` for wildcard subtypes such as
+/// `type/*` or `type/*+json`, and `2` for an exact media type.
+///
+/// # Returns
+///
+/// - `TokenStream2` - The helper function definition.
+fn media_range_helper() -> proc_macro2::TokenStream {
+    quote! {
+        fn media_range_specificity(range: &str, media_type: &str) -> ::std::option::Option<u8> {
+            let range: ::std::string::String = range.trim().to_ascii_lowercase();
+            let media_type: ::std::string::String = media_type.trim().to_ascii_lowercase();
+            let (range_main, range_sub) = range.split_once('/')?;
+            let (main, sub) = media_type.split_once('/')?;
+            if range_main == "*" && range_sub == "*" {
+                return Some(0);
+            }
+            if range_main != main {
+                return None;
+            }
+            if range_sub == "*" {
+                return Some(1);
+            }
+            match range_sub.strip_prefix('*') {
+                Some(suffix) => sub.ends_with(suffix).then_some(1),
+                None => (range_sub == sub).then_some(2),
+            }
+        }
+    }
+}
+
+/// Filters requests whose `Content-Type` matches one of the specified media types.
+///
+/// Parameters of the header such as `charset` are ignored and the comparison is case-insensitive.
+/// Requests that do not match, including those without a `Content-Type` header, are answered
+/// with `415 Unsupported Media Type` unless another response is given.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with the media type check.
+pub(crate) fn consumes_macro(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+) -> TokenStream {
+    let media_type_data: MediaTypeData = parse_macro_input!(attr as MediaTypeData);
+    if let Some(capture) = &media_type_data.capture {
+        return syn::Error::new_spanned(capture, "`consumes` cannot capture a media type")
+            .to_compile_error()
+            .into();
+    }
+    let response: GuardResponse = media_type_data.response.unwrap_or_else(|| GuardResponse {
+        status_code: parse_quote!(#UNSUPPORTED_MEDIA_TYPE_STATUS_CODE),
+        body: None,
+    });
+    let media_types: Vec<Expr> = media_type_data.media_types;
+    inject(position, item, |context: &Ident, stream: &Ident| {
+        let early_return: proc_macro2::TokenStream = guard_return(context, stream, Some(&response));
+        let helper: proc_macro2::TokenStream = media_range_helper();
+        quote! {
+            let is_supported_media_type: bool = {
+                #helper
+                #context
+                    .get_request()
+                    .try_get_header_back(::hyperlane::CONTENT_TYPE)
+                    .is_some_and(|content_type| {
+                        let media_type: &str = content_type.split(';').next().unwrap_or_default();
+                        false #(|| media_range_specificity(::std::convert::AsRef::<str>::as_ref(&#media_types), media_type).is_some())*
+                    })
+            };
+            if !is_supported_media_type {
+                #early_return
+            }
+        }
+    })
+}
+
+/// Negotiates the response media type against the `Accept` header.
+///
+/// Every produced media type is given the quality of the most specific matching range of
+/// the `Accept` header, and the one with the highest non-zero quality is chosen, preferring
+/// earlier media types on ties. A missing `Accept` header accepts the first media type.
+/// The chosen media type is set as the response `Content-Type`. When nothing is acceptable
+/// the request is answered with `406 Not Acceptable` unless another response is given.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with the content negotiation.
+pub(crate) fn produces_macro(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+) -> TokenStream {
+    let media_type_data: MediaTypeData = parse_macro_input!(attr as MediaTypeData);
+    let response: GuardResponse = media_type_data.response.unwrap_or_else(|| GuardResponse {
+        status_code: parse_quote!(#NOT_ACCEPTABLE_STATUS_CODE),
+        body: None,
+    });
+    let capture: Ident = media_type_data
+        .capture
+        .unwrap_or_else(|| Ident::new("negotiated_media_type", Span::call_site()));
+    let media_types: Vec<Expr> = media_type_data.media_types;
+    if let Err(err) = media_types
+        .iter()
+        .try_for_each(validate_concrete_media_type)
+    {
+        return err.to_compile_error().into();
+    }
+    inject(position, item, |context: &Ident, stream: &Ident| {
+        let new_context: proc_macro2::TokenStream = leak_mut_context(false, context);
+        let early_return: proc_macro2::TokenStream = guard_return(context, stream, Some(&response));
+        let helper: proc_macro2::TokenStream = media_range_helper();
+        quote! {
+            let negotiated: ::std::option::Option<::std::string::String> = {
+                #helper
+                let accept: ::std::string::String = #context
+                    .get_request()
+                    .try_get_header_back(::hyperlane::ACCEPT)
+                    .map_or_else(|| ::std::string::String::from("*/*"), |accept| accept.to_string());
+                let ranges: ::std::vec::Vec<(&str, f32)> = accept
+                    .split(',')
+                    .filter_map(|range: &str| {
+                        let mut parts = range.split(';');
+                        let media_range: &str = parts.next()?.trim();
+                        let quality: f32 = parts
+                            .filter_map(|parameter: &str| parameter.trim().strip_prefix("q="))
+                            .next()
+                            .map_or(Some(1.0), |quality: &str| quality.trim().parse::<f32>().ok())?;
+                        (!media_range.is_empty()).then_some((media_range, quality.clamp(0.0, 1.0)))
+                    })
+                    .collect();
+                let mut best: ::std::option::Option<(::std::string::String, f32)> = None;
+                #(
+                    {
+                        let media_type = #media_types;
+                        let media_type: &str = ::std::convert::AsRef::<str>::as_ref(&media_type);
+                        let quality: f32 = ranges
+                            .iter()
+                            .filter_map(|(range, quality)| {
+                                media_range_specificity(range, media_type).map(|specificity: u8| (specificity, *quality))
+                            })
+                            .max_by(|left, right| left.0.cmp(&right.0))
+                            .map_or(0.0, |(_, quality)| quality);
+                        if quality > 0.0 && best.as_ref().is_none_or(|(_, best_quality)| quality > *best_quality) {
+                            best = Some((media_type.to_owned(), quality));
+                        }
+                    }
+                )*
+                best.map(|(media_type, _)| media_type)
+            };
+            let ::std::option::Option::Some(#capture) = negotiated else {
+                #early_return
+            };
+            #new_context
+                .get_mut_response()
+                .set_header(::hyperlane::CONTENT_TYPE, &#capture);
+        }
+    })
+}
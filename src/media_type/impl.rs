@@ -0,0 +1,62 @@
+use super::*;
+
+/// Implementation of Parse trait for MediaTypeData.
+///
+/// Parses media type expressions, optionally followed by a `capture = variable` option
+/// and a `respond = ...` or `else = ...` option.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<MediaTypeData>` - Parsed MediaTypeData or error.
+impl Parse for MediaTypeData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut media_types: Vec<Expr> = Vec::new();
+        let mut capture: Option<Ident> = None;
+        let mut response: Option<GuardResponse> = None;
+        loop {
+            let fork: ParseStream = &input.fork();
+            let is_capture: bool = fork
+                .parse::<Ident>()
+                .is_ok_and(|name: Ident| name == CAPTURE_OPTION)
+                && fork.peek(Token![=])
+                && !fork.peek(Token![==]);
+            if is_capture {
+                if media_types.is_empty() || capture.is_some() {
+                    return Err(input.error(
+                        "expected at least one media type and at most one `capture` option",
+                    ));
+                }
+                input.parse::<Ident>()?;
+                input.parse::<Token![=]>()?;
+                capture = Some(input.parse()?);
+            } else if peek_guard_response(input) {
+                if media_types.is_empty() || response.is_some() {
+                    return Err(input.error(
+                        "expected at least one media type and at most one `respond` or `else` option",
+                    ));
+                }
+                response = Some(input.parse()?);
+            } else {
+                let media_type: Expr = input.parse()?;
+                validate_media_type(&media_type)?;
+                media_types.push(media_type);
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+        }
+        Ok(MediaTypeData {
+            media_types,
+            capture,
+            response,
+        })
+    }
+}
@@ -0,0 +1,14 @@
+use super::*;
+
+/// Media type data container for the `consumes` and `produces` guards.
+///
+/// Stores the media type patterns, the optional variable bound to the negotiated
+/// media type and the optional response sent when the guard stops the request.
+pub(crate) struct MediaTypeData {
+    /// Vector of media type expressions to match against.
+    pub(crate) media_types: Vec<Expr>,
+    /// The optional variable bound to the negotiated media type.
+    pub(crate) capture: Option<Ident>,
+    /// The optional response sent when the guard stops the request.
+    pub(crate) response: Option<GuardResponse>,
+}
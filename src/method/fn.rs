@@ -1,23 +1,48 @@
 use super::*;
 
+/// Generates the boolean expression checking the request method.
+///
+/// # Arguments
+///
+/// - `&Ident` - The context identifier.
+/// - `&proc_macro2::Ident` - The HTTP method name as an ident.
+///
+/// # Returns
+///
+/// - `TokenStream2` - An expression that is `true` when the request method matches.
+pub(crate) fn method_condition(
+    context: &Ident,
+    method: &proc_macro2::Ident,
+) -> proc_macro2::TokenStream {
+    let check_fn: proc_macro2::Ident = Ident::new(&format!("is_{method}"), method.span());
+    quote! {
+        #context.get_request().get_method().#check_fn()
+    }
+}
+
 /// Creates a method check function for HTTP request validation.
 ///
 /// # Arguments
 ///
 /// - `&proc_macro2::Ident` - The HTTP method name as an ident.
 ///
+/// - `Option<GuardResponse>` - The optional response sent when the check fails.
+///
 /// # Returns
 ///
 /// Returns a closure that generates the method check code.
 pub(crate) fn create_method_check(
     method: &proc_macro2::Ident,
+    response: Option<GuardResponse>,
 ) -> impl FnOnce(&Ident, &Ident) -> proc_macro2::TokenStream {
-    let method_str: String = method.to_string();
-    move |context: &Ident, _: &Ident| {
-        let check_fn: proc_macro2::Ident = Ident::new(&format!("is_{method_str}"), context.span());
+    let method: proc_macro2::Ident = method.clone();
+    move |context: &Ident, stream: &Ident| {
+        let early_return: proc_macro2::TokenStream =
+            guard_return(context, stream, response.as_ref());
+        let condition: proc_macro2::TokenStream = method_condition(context, &method);
         quote! {
-            if !#context.get_request().get_method().#check_fn() {
-                return ::hyperlane::Status::Continue;
+            if !#condition {
+                #early_return
             }
         }
     }
@@ -47,21 +72,19 @@ pub(crate) fn methods_macro(
     let sig: &Signature = &input_fn.sig;
     match parse_context_from_signature(sig) {
         Ok(context) => {
-            let method_checks = methods.methods.iter().map(|method| {
-                let method_str: String = method.to_string();
-                let check_fn: proc_macro2::Ident =
-                    Ident::new(&format!("is_{method_str}"), method.span());
-                quote! {
-                    #context.get_request().get_method().#check_fn()
-                }
-            });
+            let method_checks = methods
+                .methods
+                .iter()
+                .map(|method| method_condition(&context, method));
             inject(
                 position,
                 TokenStream::from(quote! { #input_fn }),
-                |_: &Ident, _: &Ident| {
+                |_: &Ident, stream: &Ident| {
+                    let early_return: proc_macro2::TokenStream =
+                        guard_return(&context, stream, methods.response.as_ref());
                     quote! {
                         if !(#(#method_checks)||*) {
-                            return ::hyperlane::Status::Continue;
+                            #early_return
                         }
                     }
                 },
@@ -87,15 +110,22 @@ pub(crate) fn methods_macro(
 /// Returns a macro that generates a handler function for the specified HTTP method.
 macro_rules! impl_http_method_macro {
     ($name:ident, $submit_name:ident, $method:ident) => {
-        pub(crate) fn $name(item: TokenStream, position: Position) -> TokenStream {
-            inject(
-                position,
-                item,
-                create_method_check(&proc_macro2::Ident::new(
-                    stringify!($method),
-                    Span::call_site(),
-                )),
-            )
+        pub(crate) fn $name(
+            attr: TokenStream,
+            item: TokenStream,
+            position: Position,
+        ) -> TokenStream {
+            match parse_guard_response(attr) {
+                Ok(response) => inject(
+                    position,
+                    item,
+                    create_method_check(
+                        &proc_macro2::Ident::new(stringify!($method), Span::call_site()),
+                        response,
+                    ),
+                ),
+                Err(err) => err.to_compile_error().into(),
+            }
         }
     };
 }
@@ -0,0 +1,117 @@
+use super::*;
+
+/// Limits the request rate of a handler with an in-process token bucket per key.
+///
+/// Every expansion owns its own store, so each route is limited separately. Buckets hold
+/// `limit` tokens and refill over `per`. Keys are ordered by last use, so once the store
+/// reaches `max_keys` the least recently used key is evicted without scanning the store.
+/// The default key is the client address, determined as `allow_ip` does. Requests whose
+/// client address cannot be determined have no key and are not limited, rather than sharing
+/// one bucket.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with the rate limit check.
+pub(crate) fn rate_limit_macro(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+) -> TokenStream {
+    let rate_limit: RateLimitData = parse_macro_input!(attr as RateLimitData);
+    inject(position, item, |context: &Ident, stream: &Ident| {
+        let new_context: proc_macro2::TokenStream = leak_mut_context(false, context);
+        let early_return: proc_macro2::TokenStream =
+            guard_return(context, stream, Some(&rate_limit.response));
+        let limit: &Expr = &rate_limit.limit;
+        let period_millis: u64 = rate_limit.period_millis;
+        let max_keys: &Expr = &rate_limit.max_keys;
+        let key: proc_macro2::TokenStream = match &rate_limit.key {
+            Some(key) => quote! {
+                ::std::option::Option::Some(::std::string::ToString::to_string(&#key))
+            },
+            None => {
+                let client_ip: proc_macro2::TokenStream =
+                    client_ip_statements(context, stream, &rate_limit.trusted_proxies);
+                quote! {
+                    {
+                        #client_ip
+                        client_ip.map(|client_ip: ::std::net::IpAddr| client_ip.to_string())
+                    }
+                }
+            }
+        };
+        quote! {
+            if let ::std::option::Option::Some(key) = #key {
+                struct Bucket {
+                    tokens: f64,
+                    updated: ::std::time::Instant,
+                    last_use: u64,
+                }
+                #[derive(Default)]
+                struct Store {
+                    buckets: ::std::collections::HashMap<::std::string::String, Bucket>,
+                    last_uses: ::std::collections::BTreeMap<u64, ::std::string::String>,
+                    next_use: u64,
+                }
+                static STORE: ::std::sync::LazyLock<::std::sync::Mutex<Store>> =
+                    ::std::sync::LazyLock::new(::std::default::Default::default);
+                let limit: f64 = (#limit) as f64;
+                let period: ::std::time::Duration = ::std::time::Duration::from_millis(#period_millis);
+                let rate: f64 = limit / period.as_secs_f64();
+                let max_keys: usize = #max_keys;
+                let now: ::std::time::Instant = ::std::time::Instant::now();
+                let (allowed, remaining, retry_after, reset): (bool, u64, u64, u64) = {
+                    let mut guard = STORE.lock().unwrap_or_else(::std::sync::PoisonError::into_inner);
+                    let store: &mut Store = &mut guard;
+                    if !store.buckets.contains_key(&key) {
+                        while store.buckets.len() >= max_keys {
+                            match store.last_uses.pop_first() {
+                                Some((_, oldest)) => {
+                                    store.buckets.remove(&oldest);
+                                }
+                                None => break,
+                            }
+                        }
+                    }
+                    let last_use: u64 = store.next_use;
+                    store.next_use += 1;
+                    let bucket: &mut Bucket = store.buckets.entry(key.clone()).or_insert(Bucket {
+                        tokens: limit,
+                        updated: now,
+                        last_use,
+                    });
+                    store.last_uses.remove(&bucket.last_use);
+                    store.last_uses.insert(last_use, key);
+                    bucket.last_use = last_use;
+                    bucket.tokens = (bucket.tokens + now.duration_since(bucket.updated).as_secs_f64() * rate).min(limit);
+                    bucket.updated = now;
+                    let allowed: bool = bucket.tokens >= 1.0;
+                    if allowed {
+                        bucket.tokens -= 1.0;
+                    }
+                    (
+                        allowed,
+                        bucket.tokens.floor() as u64,
+                        ((1.0 - bucket.tokens).max(0.0) / rate).ceil() as u64,
+                        ((limit - bucket.tokens) / rate).ceil() as u64,
+                    )
+                };
+                let response: &mut ::hyperlane::Response = #new_context.get_mut_response();
+                response
+                    .set_header(#RATE_LIMIT_LIMIT_HEADER, limit.to_string())
+                    .set_header(#RATE_LIMIT_REMAINING_HEADER, remaining.to_string())
+                    .set_header(#RATE_LIMIT_RESET_HEADER, reset.to_string());
+                if !allowed {
+                    response.set_header(#RETRY_AFTER_HEADER, retry_after.to_string());
+                    #early_return
+                }
+            }
+        }
+    })
+}
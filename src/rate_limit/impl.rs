@@ -0,0 +1,101 @@
+use super::*;
+
+/// Implementation of Parse trait for RateLimitData.
+///
+/// Parses a leading request limit followed by a required `per = "duration"` option and
+/// optional `key = expr`, `trusted_proxies(...)`, `max_keys = expr` and `respond = ...` or
+/// `else = ...` options.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<RateLimitData>` - Parsed RateLimitData or error.
+impl Parse for RateLimitData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let limit: Expr = input.parse()?;
+        if let Expr::Lit(ExprLit {
+            lit: Lit::Int(value),
+            ..
+        }) = &limit
+            && value.base10_parse::<u64>()? == 0
+        {
+            return Err(syn::Error::new_spanned(
+                &limit,
+                "the rate limit must allow at least one request",
+            ));
+        }
+        let mut period_millis: Option<u64> = None;
+        let mut key: Option<Expr> = None;
+        let mut has_key: bool = false;
+        let mut trusted_proxies: Option<Vec<(IpAddr, u8)>> = None;
+        let mut max_keys: Option<Expr> = None;
+        let mut response: Option<GuardResponse> = None;
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            if peek_guard_response(input) {
+                if response.is_some() {
+                    return Err(input.error("at most one `respond` or `else` option is allowed"));
+                }
+                response = Some(input.parse()?);
+                continue;
+            }
+            let name: Ident = input.parse()?;
+            if name == IP_TRUSTED_PROXIES_OPTION && trusted_proxies.is_none() {
+                let content;
+                parenthesized!(content in input);
+                trusted_proxies = Some(
+                    Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?
+                        .iter()
+                        .map(parse_ip_range)
+                        .collect::<syn::Result<_>>()?,
+                );
+                continue;
+            }
+            input.parse::<Token![=]>()?;
+            if name == RATE_LIMIT_PER_OPTION && period_millis.is_none() {
+                period_millis = Some(parse_duration_millis(&input.parse()?)?);
+            } else if name == RATE_LIMIT_KEY_OPTION && !has_key {
+                has_key = true;
+                let value: Expr = input.parse()?;
+                let is_client_ip: bool = matches!(&value, Expr::Path(path) if path.path.is_ident(RATE_LIMIT_CLIENT_IP_KEY));
+                key = (!is_client_ip).then_some(value);
+            } else if name == RATE_LIMIT_MAX_KEYS_OPTION && max_keys.is_none() {
+                max_keys = Some(input.parse()?);
+            } else {
+                return Err(syn::Error::new_spanned(
+                    &name,
+                    "expected a single `per`, `key`, `trusted_proxies`, `max_keys`, `respond` or `else` option",
+                ));
+            }
+        }
+        let Some(period_millis) = period_millis else {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "expected a `per = \"duration\"` option, such as `per = \"1m\"`",
+            ));
+        };
+        if let (Some(key), Some(_)) = (&key, &trusted_proxies) {
+            return Err(syn::Error::new_spanned(
+                key,
+                "`trusted_proxies` only applies to the `client_ip` key",
+            ));
+        }
+        Ok(RateLimitData {
+            limit,
+            period_millis,
+            key,
+            trusted_proxies: trusted_proxies.unwrap_or_default(),
+            max_keys: max_keys.unwrap_or_else(|| parse_quote!(#RATE_LIMIT_DEFAULT_MAX_KEYS)),
+            response: response.unwrap_or_else(|| GuardResponse {
+                status_code: parse_quote!(#RATE_LIMIT_STATUS_CODE),
+                body: None,
+            }),
+        })
+    }
+}
@@ -0,0 +1,20 @@
+use super::*;
+
+/// Rate limit data container for the `rate_limit` macro.
+///
+/// Stores the bucket size, its refill period, the key expression, the trusted proxies,
+/// the store size and the response sent when the limit is exceeded.
+pub(crate) struct RateLimitData {
+    /// The number of requests allowed per period.
+    pub(crate) limit: Expr,
+    /// The refill period in milliseconds.
+    pub(crate) period_millis: u64,
+    /// The optional key expression, `None` to key by the client address.
+    pub(crate) key: Option<Expr>,
+    /// The proxies trusted to report the client address in forwarding headers.
+    pub(crate) trusted_proxies: Vec<(IpAddr, u8)>,
+    /// The maximum number of keys kept in the store.
+    pub(crate) max_keys: Expr,
+    /// The response sent when the limit is exceeded.
+    pub(crate) response: GuardResponse,
+}
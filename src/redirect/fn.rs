@@ -0,0 +1,41 @@
+use super::*;
+
+/// Sends a redirect response and stops the handler.
+///
+/// Sets the status code, its reason phrase and the `Location` header, clears the body,
+/// sends the response and returns `Status::Reject` so no later hook sends again.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with the redirect response.
+pub(crate) fn redirect_macro(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+) -> TokenStream {
+    let redirect_data: RedirectData = parse_macro_input!(attr as RedirectData);
+    let status_code: Expr = redirect_data.status_code;
+    let location: Expr = redirect_data.location;
+    inject(position, item, |context: &Ident, stream: &Ident| {
+        let new_context: proc_macro2::TokenStream = leak_mut_context(false, context);
+        quote! {
+            if true {
+                let status_code: ::hyperlane::ResponseStatusCode = ::hyperlane::ResponseStatusCode::from(#status_code as usize);
+                let response: &mut ::hyperlane::Response = #new_context.get_mut_response();
+                response
+                    .set_status_code(status_code)
+                    .set_reason_phrase(::hyperlane::HttpStatus::phrase(status_code))
+                    .set_header(::hyperlane::LOCATION, &#location)
+                    .set_body(::hyperlane::ResponseBody::new());
+                let _: ::std::result::Result<(), ::hyperlane::ResponseError> = #stream.try_send(response.build()).await;
+                return ::hyperlane::Status::Reject;
+            }
+        }
+    })
+}
@@ -0,0 +1,32 @@
+use super::*;
+
+/// Implementation of Parse trait for RedirectData.
+///
+/// Parses `status_code => location` from input stream, rejecting literal status codes
+/// that are not supported redirect codes.
+impl Parse for RedirectData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let status_code: Expr = input.parse()?;
+        if let Expr::Lit(ExprLit {
+            lit: Lit::Int(lit_int),
+            ..
+        }) = &status_code
+        {
+            let code: u16 = lit_int.base10_parse()?;
+            if !REDIRECT_STATUS_CODES.contains(&code) {
+                return Err(syn::Error::new_spanned(
+                    lit_int,
+                    format!(
+                        "unsupported redirect status code `{code}`, expected one of 301, 302, 303, 307 or 308"
+                    ),
+                ));
+            }
+        }
+        input.parse::<Token![=>]>()?;
+        let location: Expr = input.parse()?;
+        Ok(RedirectData {
+            status_code,
+            location,
+        })
+    }
+}
@@ -0,0 +1,11 @@
+use super::*;
+
+/// Represents data for a redirect response.
+///
+/// This struct holds the redirect status code and the target location.
+pub(crate) struct RedirectData {
+    /// The redirect status code.
+    pub(crate) status_code: Expr,
+    /// The target location.
+    pub(crate) location: Expr,
+}
@@ -0,0 +1,20 @@
+use super::*;
+
+/// Defines how a header value is matched against a referer or origin pattern.
+pub(crate) enum RefererPattern {
+    /// Matches when the header equals the value.
+    Exact(Expr),
+    /// Matches when the scheme, host and port of the header equal those of the value.
+    /// The host of the value may start with a `*.` wildcard label.
+    Origin(Expr),
+    /// Matches when the header starts with the value.
+    Prefix(Expr),
+}
+
+/// Defines how the `origin` guard treats a request without an `Origin` header.
+pub(crate) enum MissingHeader {
+    /// Lets the request through.
+    Allow,
+    /// Stops the request.
+    Deny,
+}
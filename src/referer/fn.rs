@@ -1,7 +1,217 @@
 use super::*;
 
-/// Filters requests matching the specified Referer header.
-/// Supports both single and multiple referer value checks.
+/// Validates a literal origin or prefix pattern.
+///
+/// The pattern must have the form `scheme://host[:port]`, optionally followed by a single `/`,
+/// or by a path for prefix patterns. A `*` is only allowed as the leading `*.` label of the host.
+///
+/// # Arguments
+///
+/// - `&Expr` - The pattern expression.
+/// - `bool` - Whether the pattern may contain a path.
+///
+/// # Returns
+///
+/// - `syn::Result<()>` - An error spanned at the pattern when a literal pattern is malformed.
+pub(crate) fn validate_origin_pattern(origin_value: &Expr, allow_path: bool) -> syn::Result<()> {
+    let Expr::Lit(ExprLit {
+        lit: Lit::Str(pattern),
+        ..
+    }) = origin_value
+    else {
+        return Ok(());
+    };
+    let pattern: String = pattern.value();
+    let form: &str = if allow_path {
+        "prefix patterns must have the form `scheme://host[:port][/path]`"
+    } else {
+        "origin patterns must have the form `scheme://host[:port]` without a path"
+    };
+    let Some((scheme, rest)) = pattern.split_once("://") else {
+        return Err(syn::Error::new_spanned(origin_value, form));
+    };
+    let authority: &str = if allow_path {
+        rest.split(['/', '?', '#']).next().unwrap_or_default()
+    } else {
+        rest.strip_suffix('/').unwrap_or(rest)
+    };
+    if scheme.is_empty() || authority.is_empty() || authority.contains(['/', '?', '#', '@']) {
+        return Err(syn::Error::new_spanned(origin_value, form));
+    }
+    if authority
+        .strip_prefix("*.")
+        .unwrap_or(authority)
+        .contains('*')
+    {
+        return Err(syn::Error::new_spanned(
+            origin_value,
+            "patterns support `*` only as a leading `*.` label of the host",
+        ));
+    }
+    if ::hyperlane::HttpUrlComponents::parse(&pattern).is_err() {
+        return Err(syn::Error::new_spanned(
+            origin_value,
+            format!("`{pattern}` is not a valid URL"),
+        ));
+    }
+    Ok(())
+}
+
+/// Generates the boolean expression matching a parsed URL against an origin or prefix pattern.
+///
+/// URLs are parsed with `HttpUrlComponents`, so schemes and hosts are compared case-insensitively,
+/// default ports are implied and dot segments are removed from paths. A prefix pattern matches
+/// when the origins match and the path of the URL equals the path of the pattern or continues it
+/// at a `/` boundary.
+///
+/// # Arguments
+///
+/// - `&Ident` - The identifier of the `Option<&HttpUrlComponents>` to match.
+/// - `&Expr` - The pattern expression.
+/// - `bool` - Whether the pattern is a prefix pattern.
+///
+/// # Returns
+///
+/// - `TokenStream2` - An expression that is `true` when the URL matches the pattern.
+fn url_pattern_condition(url: &Ident, pattern: &Expr, is_prefix: bool) -> proc_macro2::TokenStream {
+    let path_condition: proc_macro2::TokenStream = if is_prefix {
+        quote! {
+            && {
+                let path: &str = url.path.as_deref().unwrap_or("/");
+                let prefix: &str = pattern.path.as_deref().unwrap_or("/");
+                path.strip_prefix(prefix).is_some_and(|rest: &str| {
+                    prefix.ends_with('/') || rest.is_empty() || rest.starts_with('/')
+                })
+            }
+        }
+    } else {
+        quote! {}
+    };
+    quote! {
+        #url
+            .zip(::hyperlane::HttpUrlComponents::parse(::std::convert::AsRef::<str>::as_ref(&#pattern)).ok())
+            .is_some_and(|(url, pattern): (&::hyperlane::HttpUrlComponents, ::hyperlane::HttpUrlComponents)| {
+                url.protocol == pattern.protocol
+                    && url.port == pattern.port
+                    && match (url.host.as_deref(), pattern.host.as_deref()) {
+                        (Some(host), Some(pattern_host)) => match pattern_host.strip_prefix("*.") {
+                            Some(suffix) => host
+                                .strip_suffix(suffix)
+                                .and_then(|subdomain: &str| subdomain.strip_suffix('.'))
+                                .is_some_and(|subdomain: &str| !subdomain.is_empty()),
+                            None => host == pattern_host,
+                        },
+                        _ => false,
+                    }
+                    #path_condition
+            })
+    }
+}
+
+/// Generates the boolean expression matching a header value against one referer pattern.
+///
+/// # Arguments
+///
+/// - `&Ident` - The identifier of the `&str` header value.
+/// - `&Ident` - The identifier of the header value parsed as an `Option<&HttpUrlComponents>`.
+/// - `&RefererPattern` - The referer pattern.
+///
+/// # Returns
+///
+/// - `TokenStream2` - An expression that is `true` when the header value matches the pattern.
+fn referer_pattern_condition(
+    header: &Ident,
+    url: &Ident,
+    pattern: &RefererPattern,
+) -> proc_macro2::TokenStream {
+    match pattern {
+        RefererPattern::Exact(value) => quote! {
+            #header == ::std::convert::AsRef::<str>::as_ref(&#value)
+        },
+        RefererPattern::Origin(value) => url_pattern_condition(url, value, false),
+        RefererPattern::Prefix(value) => url_pattern_condition(url, value, true),
+    }
+}
+
+/// Generates the boolean expression checking the Referer header against several patterns.
+///
+/// # Arguments
+///
+/// - `&Ident` - The context identifier.
+/// - `&[RefererPattern]` - The referer patterns.
+///
+/// # Returns
+///
+/// - `TokenStream2` - An expression that is `true` when the Referer header is present and matches any of the patterns.
+pub(crate) fn referer_condition(
+    context: &Ident,
+    referer_values: &[RefererPattern],
+) -> proc_macro2::TokenStream {
+    let header: Ident = Ident::new("referer_header", Span::call_site());
+    let url: Ident = Ident::new("referer_url", Span::call_site());
+    let conditions = referer_values
+        .iter()
+        .map(|pattern: &RefererPattern| referer_pattern_condition(&header, &url, pattern));
+    let parse_url: Option<proc_macro2::TokenStream> = referer_values
+        .iter()
+        .any(|pattern: &RefererPattern| !matches!(pattern, RefererPattern::Exact(_)))
+        .then(|| {
+            quote! {
+                let #url: ::std::option::Option<::hyperlane::HttpUrlComponents> =
+                    ::hyperlane::HttpUrlComponents::parse(#header).ok();
+                let #url: ::std::option::Option<&::hyperlane::HttpUrlComponents> = #url.as_ref();
+            }
+        });
+    quote! {
+        #context
+            .get_request()
+            .try_get_header_back(::hyperlane::REFERER)
+            .is_some_and(|#header| {
+                let #header: &str = &#header;
+                #parse_url
+                #(#conditions)||*
+            })
+    }
+}
+
+/// Generates the boolean expression checking the Origin header against several origin patterns.
+///
+/// # Arguments
+///
+/// - `&Ident` - The context identifier.
+/// - `&[Expr]` - The origin pattern expressions.
+/// - `&MissingHeader` - How a request without an `Origin` header is treated.
+///
+/// # Returns
+///
+/// - `TokenStream2` - An expression that is `true` when the Origin header matches any of the patterns.
+pub(crate) fn origin_condition(
+    context: &Ident,
+    origin_values: &[Expr],
+    missing: &MissingHeader,
+) -> proc_macro2::TokenStream {
+    let url: Ident = Ident::new("origin_url", Span::call_site());
+    let conditions = origin_values
+        .iter()
+        .map(|pattern: &Expr| url_pattern_condition(&url, pattern, false));
+    let missing: bool = matches!(missing, MissingHeader::Allow);
+    quote! {
+        {
+            match #context.get_request().try_get_header_back(::hyperlane::ORIGIN) {
+                Some(origin_header) => {
+                    let #url: ::std::option::Option<::hyperlane::HttpUrlComponents> =
+                        ::hyperlane::HttpUrlComponents::parse(&origin_header).ok();
+                    let #url: ::std::option::Option<&::hyperlane::HttpUrlComponents> = #url.as_ref();
+                    false #(|| #conditions)*
+                }
+                None => #missing,
+            }
+        }
+    }
+}
+
+/// Filters requests whose Referer header matches one of the specified patterns.
+/// Supports exact values, `origin(...)` and `prefix(...)` patterns.
 ///
 /// # Arguments
 ///
@@ -18,22 +228,21 @@ pub(crate) fn referer_macro(
     position: Position,
 ) -> TokenStream {
     let multi_referer: MultiRefererData = parse_macro_input!(attr as MultiRefererData);
-    inject(position, item, |context: &Ident, _: &Ident| {
-        let statements = multi_referer.referer_values.iter().map(|referer_value| {
-            quote! {
-                if #context.get_request().try_get_header_back(::hyperlane::REFERER).map_or(true, |referer_header| referer_header != #referer_value) {
-                    return ::hyperlane::Status::Continue;
-                }
-            }
-        });
+    inject(position, item, |context: &Ident, stream: &Ident| {
+        let early_return: proc_macro2::TokenStream =
+            guard_return(context, stream, multi_referer.response.as_ref());
+        let condition: proc_macro2::TokenStream =
+            referer_condition(context, &multi_referer.referer_values);
         quote! {
-            #(#statements)*
+            if !#condition {
+                #early_return
+            }
         }
     })
 }
 
-/// Rejects requests matching the specified Referer header.
-/// Supports both single and multiple referer value checks.
+/// Rejects requests whose Referer header matches one of the specified patterns.
+/// Supports exact values, `origin(...)` and `prefix(...)` patterns.
 ///
 /// # Arguments
 ///
@@ -50,16 +259,45 @@ pub(crate) fn reject_referer_macro(
     position: Position,
 ) -> TokenStream {
     let multi_referer: MultiRefererData = parse_macro_input!(attr as MultiRefererData);
-    inject(position, item, |context: &Ident, _: &Ident| {
-        let statements = multi_referer.referer_values.iter().map(|referer_value| {
-            quote! {
-                if #context.get_request().try_get_header_back(::hyperlane::REFERER).map_or(false, |referer_header| referer_header == #referer_value) {
-                    return ::hyperlane::Status::Continue;
-                }
+    inject(position, item, |context: &Ident, stream: &Ident| {
+        let early_return: proc_macro2::TokenStream =
+            guard_return(context, stream, multi_referer.response.as_ref());
+        let condition: proc_macro2::TokenStream =
+            referer_condition(context, &multi_referer.referer_values);
+        quote! {
+            if #condition {
+                #early_return
             }
-        });
+        }
+    })
+}
+
+/// Filters requests whose Origin header matches one of the specified origin patterns.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with Origin filter.
+pub(crate) fn origin_macro(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+) -> TokenStream {
+    let origin_data: OriginData = parse_macro_input!(attr as OriginData);
+    inject(position, item, |context: &Ident, stream: &Ident| {
+        let early_return: proc_macro2::TokenStream =
+            guard_return(context, stream, origin_data.response.as_ref());
+        let condition: proc_macro2::TokenStream =
+            origin_condition(context, &origin_data.origin_values, &origin_data.missing);
         quote! {
-            #(#statements)*
+            if !#condition {
+                #early_return
+            }
         }
     })
 }
@@ -1,9 +1,49 @@
 use super::*;
 
+/// Implementation of Parse trait for RefererPattern.
+///
+/// Parses `origin(value)`, `prefix(value)` or a plain value matched exactly.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<RefererPattern>` - Parsed RefererPattern or error.
+impl Parse for RefererPattern {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let fork: ParseStream = &input.fork();
+        let mode: Option<Ident> = fork
+            .parse::<Ident>()
+            .ok()
+            .filter(|mode: &Ident| mode == REFERER_ORIGIN_MODE || mode == REFERER_PREFIX_MODE)
+            .filter(|_| fork.peek(token::Paren));
+        let Some(mode) = mode else {
+            return Ok(RefererPattern::Exact(input.parse()?));
+        };
+        input.parse::<Ident>()?;
+        let content;
+        parenthesized!(content in input);
+        let value: Expr = content.parse()?;
+        if !content.is_empty() {
+            return Err(content.error(format!("`{mode}` expects exactly one value")));
+        }
+        if mode == REFERER_ORIGIN_MODE {
+            validate_origin_pattern(&value, false)?;
+            Ok(RefererPattern::Origin(value))
+        } else {
+            validate_origin_pattern(&value, true)?;
+            Ok(RefererPattern::Prefix(value))
+        }
+    }
+}
+
 /// Implementation of Parse trait for MultiRefererData.
 ///
-/// Parses referer value expressions from input stream.
-/// Supports both single and multiple referer values.
+/// Parses referer patterns from input stream.
+/// Supports both single and multiple referer values, optionally followed by
+/// a `respond = ...` or `else = ...` option.
 ///
 /// # Arguments
 ///
@@ -14,10 +54,107 @@ use super::*;
 /// - `syn::Result<MultiRefererData>` - Parsed MultiRefererData or error.
 impl Parse for MultiRefererData {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let mut referer_values: Vec<Expr> = Vec::new();
+        let mut referer_values: Vec<RefererPattern> = Vec::new();
+        let mut response: Option<GuardResponse> = None;
+        loop {
+            if peek_guard_response(input) {
+                if referer_values.is_empty() || response.is_some() {
+                    return Err(input.error(
+                        "expected at least one referer value and at most one `respond` or `else` option",
+                    ));
+                }
+                response = Some(input.parse()?);
+            } else {
+                let referer_value: RefererPattern = input.parse()?;
+                referer_values.push(referer_value);
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+        }
+        Ok(MultiRefererData {
+            referer_values,
+            response,
+        })
+    }
+}
+
+/// Implementation of Parse trait for MissingHeader.
+///
+/// Parses `allow` or `deny`.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<MissingHeader>` - Parsed MissingHeader or error.
+impl Parse for MissingHeader {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let value: Ident = input.parse()?;
+        if value == ORIGIN_MISSING_ALLOW {
+            Ok(MissingHeader::Allow)
+        } else if value == ORIGIN_MISSING_DENY {
+            Ok(MissingHeader::Deny)
+        } else {
+            Err(syn::Error::new_spanned(
+                value,
+                "expected `allow` or `deny` for a missing header",
+            ))
+        }
+    }
+}
+
+/// Implementation of Parse trait for OriginData.
+///
+/// Parses origin patterns, optionally followed by a `missing = allow|deny` option
+/// and a `respond = ...` or `else = ...` option.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<OriginData>` - Parsed OriginData or error.
+impl Parse for OriginData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut origin_values: Vec<Expr> = Vec::new();
+        let mut missing: Option<MissingHeader> = None;
+        let mut response: Option<GuardResponse> = None;
         loop {
-            let referer_value: Expr = input.parse()?;
-            referer_values.push(referer_value);
+            let fork: ParseStream = &input.fork();
+            let is_missing: bool = fork
+                .parse::<Ident>()
+                .is_ok_and(|name: Ident| name == ORIGIN_MISSING_OPTION)
+                && fork.peek(Token![=])
+                && !fork.peek(Token![==]);
+            if is_missing {
+                if origin_values.is_empty() || missing.is_some() {
+                    return Err(input.error(
+                        "expected at least one origin value and at most one `missing` option",
+                    ));
+                }
+                input.parse::<Ident>()?;
+                input.parse::<Token![=]>()?;
+                missing = Some(input.parse()?);
+            } else if peek_guard_response(input) {
+                if origin_values.is_empty() || response.is_some() {
+                    return Err(input.error(
+                        "expected at least one origin value and at most one `respond` or `else` option",
+                    ));
+                }
+                response = Some(input.parse()?);
+            } else {
+                let origin_value: Expr = input.parse()?;
+                validate_origin_pattern(&origin_value, false)?;
+                origin_values.push(origin_value);
+            }
             if input.is_empty() {
                 break;
             }
@@ -26,6 +163,10 @@ impl Parse for MultiRefererData {
                 break;
             }
         }
-        Ok(MultiRefererData { referer_values })
+        Ok(OriginData {
+            origin_values,
+            missing: missing.unwrap_or(MissingHeader::Deny),
+            response,
+        })
     }
 }
@@ -1,7 +1,8 @@
+mod r#enum;
 mod r#fn;
 mod r#impl;
 mod r#struct;
 
-pub(crate) use {r#fn::*, r#struct::*};
+pub(crate) use {r#enum::*, r#fn::*, r#struct::*};
 
 use super::*;
@@ -1,10 +1,24 @@
 use super::*;
 
-/// Referer data container storing referer value expressions.
+/// Referer data container storing referer patterns.
 ///
 /// Used for Referer header matching in request processing.
 /// Supports both single and multiple referer values.
 pub(crate) struct MultiRefererData {
-    /// Vector of referer value expressions to match against.
-    pub(crate) referer_values: Vec<Expr>,
+    /// Vector of referer patterns to match against.
+    pub(crate) referer_values: Vec<RefererPattern>,
+    /// The optional response sent when the guard stops the request.
+    pub(crate) response: Option<GuardResponse>,
+}
+
+/// Origin data container for the `origin` guard.
+///
+/// Stores the origin patterns, the handling of a missing header and the optional response.
+pub(crate) struct OriginData {
+    /// Vector of origin pattern expressions to match against.
+    pub(crate) origin_values: Vec<Expr>,
+    /// How a request without an `Origin` header is treated.
+    pub(crate) missing: MissingHeader,
+    /// The optional response sent when the guard stops the request.
+    pub(crate) response: Option<GuardResponse>,
 }
@@ -3,10 +3,12 @@ use super::*;
 /// Rejects requests based on a boolean condition.
 ///
 /// The function returns early if the provided code block returns `true`.
+/// An optional `respond = ...` or `else = ...` option sends a response before returning.
+/// Conditions prefixed with `async` are awaited before they are checked.
 ///
 /// # Arguments
 ///
-/// - `TokenStream` - A code block that returns a boolean value.
+/// - `TokenStream` - A code block that returns a boolean value, optionally followed by a guard response.
 /// - `TokenStream` - The function to which the attribute is applied.
 /// - `Position` - The position to inject the code.
 ///
@@ -20,11 +22,14 @@ pub(crate) fn reject_macro(
     item: TokenStream,
     position: Position,
 ) -> TokenStream {
-    let condition: Expr = parse_macro_input!(attr as Expr);
-    inject(position, item, |_: &Ident, _: &Ident| {
+    let condition_data: ConditionData = parse_macro_input!(attr as ConditionData);
+    let condition: Expr = condition_data.condition;
+    inject(position, item, |context: &Ident, stream: &Ident| {
+        let early_return: proc_macro2::TokenStream =
+            guard_return(context, stream, condition_data.response.as_ref());
         quote! {
             if #condition {
-                return ::hyperlane::Status::Continue;
+                #early_return
             }
         }
     })
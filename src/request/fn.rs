@@ -93,6 +93,64 @@ pub(crate) fn request_body_json_macro(
     })
 }
 
+/// Rejects requests whose body exceeds a maximum size.
+///
+/// Both the declared `Content-Length` and the length of the body that was read are checked.
+/// Oversized requests are answered with `413 Payload Too Large` unless another response is given.
+/// Body extractors stacked below the attribute would parse the body before the check runs,
+/// so they are rejected.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with the body size check.
+pub(crate) fn max_body_size_macro(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+) -> TokenStream {
+    let body_size: BodySizeData = parse_macro_input!(attr as BodySizeData);
+    if let Ok(input_fn) = parse::<ItemFn>(item.clone())
+        && let Some(extractor) = REQUEST_BODY_EXTRACTOR_MACROS
+            .iter()
+            .find(|extractor: &&&str| has_attribute(&input_fn.attrs, extractor))
+    {
+        return syn::Error::new(
+            Span::call_site(),
+            format!("`max_body_size` must be placed below `{extractor}` so oversized bodies are rejected before they are parsed"),
+        )
+        .to_compile_error()
+        .into();
+    }
+    let response: GuardResponse = body_size.response.unwrap_or_else(|| GuardResponse {
+        status_code: parse_quote!(#PAYLOAD_TOO_LARGE_STATUS_CODE),
+        body: None,
+    });
+    let limit: Expr = body_size.limit;
+    inject(position, item, |context: &Ident, stream: &Ident| {
+        let early_return: proc_macro2::TokenStream = guard_return(context, stream, Some(&response));
+        quote! {
+            let is_body_too_large: bool = {
+                let max_body_size: usize = #limit;
+                let request: &::hyperlane::Request = #context.get_request();
+                request
+                    .try_get_header_back(::hyperlane::CONTENT_LENGTH)
+                    .and_then(|content_length| content_length.trim().parse::<u64>().ok())
+                    .is_some_and(|content_length: u64| content_length > max_body_size as u64)
+                    || request.get_body().len() > max_body_size
+            };
+            if is_body_too_large {
+                #early_return
+            }
+        }
+    })
+}
+
 /// Gets request attribute by key and assigns to specified variable.
 /// Supports both single and multiple attribute extraction.
 ///
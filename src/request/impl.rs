@@ -2,7 +2,8 @@ use super::*;
 
 /// Implementation of Parse trait for RequestMethods.
 ///
-/// Parses HTTP methods from input stream.
+/// Parses HTTP methods from input stream, optionally followed by
+/// a `respond = ...` or `else = ...` option.
 ///
 /// # Arguments
 ///
@@ -13,9 +14,26 @@ use super::*;
 /// - `syn::Result<RequestMethods>` - Parsed RequestMethods or error.
 impl Parse for RequestMethods {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        Ok(RequestMethods {
-            methods: Punctuated::parse_separated_nonempty(input)?,
-        })
+        let mut methods: Punctuated<Ident, Token![,]> = Punctuated::new();
+        let mut response: Option<GuardResponse> = None;
+        loop {
+            if !methods.is_empty() && peek_guard_response(input) {
+                response = Some(input.parse()?);
+                if !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                }
+                break;
+            }
+            methods.push_value(input.parse()?);
+            if input.is_empty() {
+                break;
+            }
+            methods.push_punct(input.parse()?);
+            if input.is_empty() {
+                break;
+            }
+        }
+        Ok(RequestMethods { methods, response })
     }
 }
 
@@ -512,3 +530,38 @@ impl Parse for MultiRequestErrorData {
         Ok(MultiRequestErrorData { variables })
     }
 }
+
+/// Implementation of Parse trait for BodySizeData.
+///
+/// Parses the maximum body size optionally followed by a `respond = ...` or `else = ...` option.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<BodySizeData>` - Parsed BodySizeData or error.
+impl Parse for BodySizeData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let limit: Expr = input.parse()?;
+        if let Expr::Lit(ExprLit {
+            lit: Lit::Int(value),
+            ..
+        }) = &limit
+        {
+            value.base10_parse::<usize>()?;
+        }
+        let mut response: Option<GuardResponse> = None;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if !input.is_empty() {
+                response = Some(input.parse()?);
+                if !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                }
+            }
+        }
+        Ok(BodySizeData { limit, response })
+    }
+}
@@ -6,6 +6,8 @@ use super::*;
 pub(crate) struct RequestMethods {
     /// The parsed HTTP methods as punctuated identifiers.
     pub(crate) methods: Punctuated<Ident, Token![,]>,
+    /// The optional response sent when the guard stops the request.
+    pub(crate) response: Option<GuardResponse>,
 }
 
 /// Container for request body data.
@@ -151,3 +153,13 @@ pub(crate) struct MultiRequestErrorData {
     /// Vector of request error data variables.
     pub(crate) variables: Vec<Ident>,
 }
+
+/// Body size data container for the `max_body_size` guard.
+///
+/// Stores the maximum body size in bytes and the optional response sent when it is exceeded.
+pub(crate) struct BodySizeData {
+    /// The maximum body size expression.
+    pub(crate) limit: Expr,
+    /// The optional response sent when the guard stops the request.
+    pub(crate) response: Option<GuardResponse>,
+}
@@ -0,0 +1,17 @@
+/// Defines where a required request value is looked up.
+#[derive(Clone, Copy)]
+pub(crate) enum RequireSource {
+    /// A request header, looked up case-insensitively.
+    Header,
+    /// A query parameter.
+    Query,
+}
+
+/// Defines how a present request value is checked.
+#[derive(Clone, Copy)]
+pub(crate) enum RequireCheck {
+    /// The value must equal the expected value.
+    Equals,
+    /// The value must be accepted by the matcher's `is_match` method.
+    Matches,
+}
@@ -0,0 +1,258 @@
+use super::*;
+
+/// Generates the expression looking up a request header or query value.
+///
+/// # Arguments
+///
+/// - `&Ident` - The context identifier.
+/// - `RequireSource` - Where the value is looked up.
+/// - `&Expr` - The key expression.
+///
+/// # Returns
+///
+/// - `TokenStream2` - An `Option` expression holding the value when present.
+fn require_lookup(context: &Ident, source: RequireSource, key: &Expr) -> proc_macro2::TokenStream {
+    match source {
+        RequireSource::Header => quote! {
+            #context
+                .get_request()
+                .try_get_header_back(::std::convert::AsRef::<str>::as_ref(&#key).to_ascii_lowercase())
+        },
+        RequireSource::Query => quote! {
+            #context.get_request().try_get_query(#key)
+        },
+    }
+}
+
+/// Generates a guard requiring request headers or query parameters to be present.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+/// - `RequireSource` - Where the values are looked up.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with the presence check.
+fn require_presence(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+    source: RequireSource,
+) -> TokenStream {
+    let multi_require: MultiRequireData = parse_macro_input!(attr as MultiRequireData);
+    inject(position, item, |context: &Ident, stream: &Ident| {
+        let early_return: proc_macro2::TokenStream =
+            guard_return(context, stream, multi_require.response.as_ref());
+        let conditions = multi_require.keys.iter().map(|key: &Expr| {
+            let lookup: proc_macro2::TokenStream = require_lookup(context, source, key);
+            quote! { #lookup.is_some() }
+        });
+        quote! {
+            if !(#(#conditions)&&*) {
+                #early_return
+            }
+        }
+    })
+}
+
+/// Generates a guard checking the values of request headers or query parameters.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+/// - `RequireSource` - Where the values are looked up.
+/// - `RequireCheck` - How the values are checked.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with the value check.
+fn require_value(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+    source: RequireSource,
+    check: RequireCheck,
+) -> TokenStream {
+    let multi_require: MultiRequireValueData = parse_macro_input!(attr as MultiRequireValueData);
+    if let RequireCheck::Matches = check
+        && let Some((_, matcher)) = multi_require.params.iter().find(|(_, matcher)| {
+            matches!(
+                matcher,
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(_),
+                    ..
+                })
+            )
+        })
+    {
+        return syn::Error::new_spanned(
+            matcher,
+            "expected a matcher with an `is_match(&str) -> bool` method, such as a `regex::Regex`, not a string literal",
+        )
+        .to_compile_error()
+        .into();
+    }
+    inject(position, item, |context: &Ident, stream: &Ident| {
+        let early_return: proc_macro2::TokenStream =
+            guard_return(context, stream, multi_require.response.as_ref());
+        let conditions = multi_require.params.iter().map(|(key, value)| {
+            let lookup: proc_macro2::TokenStream = require_lookup(context, source, key);
+            match check {
+                RequireCheck::Equals => quote! {
+                    #lookup.is_some_and(|value| value == ::std::convert::AsRef::<str>::as_ref(&#value))
+                },
+                RequireCheck::Matches => quote! {
+                    #lookup.is_some_and(|value| (#value).is_match(&value))
+                },
+            }
+        });
+        quote! {
+            if !(#(#conditions)&&*) {
+                #early_return
+            }
+        }
+    })
+}
+
+/// Filters requests that carry all of the specified headers.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with the header presence check.
+pub(crate) fn require_header_macro(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+) -> TokenStream {
+    require_presence(attr, item, position, RequireSource::Header)
+}
+
+/// Filters requests whose headers equal the specified values.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with the header equality check.
+pub(crate) fn header_eq_macro(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+) -> TokenStream {
+    require_value(
+        attr,
+        item,
+        position,
+        RequireSource::Header,
+        RequireCheck::Equals,
+    )
+}
+
+/// Filters requests whose headers are accepted by the specified matchers.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with the header pattern check.
+pub(crate) fn header_matches_macro(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+) -> TokenStream {
+    require_value(
+        attr,
+        item,
+        position,
+        RequireSource::Header,
+        RequireCheck::Matches,
+    )
+}
+
+/// Filters requests that carry all of the specified query parameters.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with the query presence check.
+pub(crate) fn require_query_macro(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+) -> TokenStream {
+    require_presence(attr, item, position, RequireSource::Query)
+}
+
+/// Filters requests whose query parameters equal the specified values.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with the query equality check.
+pub(crate) fn query_eq_macro(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+) -> TokenStream {
+    require_value(
+        attr,
+        item,
+        position,
+        RequireSource::Query,
+        RequireCheck::Equals,
+    )
+}
+
+/// Filters requests whose query parameters are accepted by the specified matchers.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with the query pattern check.
+pub(crate) fn query_matches_macro(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+) -> TokenStream {
+    require_value(
+        attr,
+        item,
+        position,
+        RequireSource::Query,
+        RequireCheck::Matches,
+    )
+}
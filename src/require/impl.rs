@@ -0,0 +1,85 @@
+use super::*;
+
+/// Implementation of Parse trait for MultiRequireData.
+///
+/// Parses key expressions from input stream.
+/// Supports both single and multiple keys, optionally followed by
+/// a `respond = ...` or `else = ...` option.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<MultiRequireData>` - Parsed MultiRequireData or error.
+impl Parse for MultiRequireData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut keys: Vec<Expr> = Vec::new();
+        let mut response: Option<GuardResponse> = None;
+        loop {
+            if peek_guard_response(input) {
+                if keys.is_empty() || response.is_some() {
+                    return Err(input.error(
+                        "expected at least one key and at most one `respond` or `else` option",
+                    ));
+                }
+                response = Some(input.parse()?);
+            } else {
+                let key: Expr = input.parse()?;
+                keys.push(key);
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+        }
+        Ok(MultiRequireData { keys, response })
+    }
+}
+
+/// Implementation of Parse trait for MultiRequireValueData.
+///
+/// Parses `key => value` pairs from input stream.
+/// Supports both single and multiple pairs, optionally followed by
+/// a `respond = ...` or `else = ...` option.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<MultiRequireValueData>` - Parsed MultiRequireValueData or error.
+impl Parse for MultiRequireValueData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut params: Vec<(Expr, Expr)> = Vec::new();
+        let mut response: Option<GuardResponse> = None;
+        loop {
+            if peek_guard_response(input) {
+                if params.is_empty() || response.is_some() {
+                    return Err(input.error(
+                        "expected at least one `key => value` pair and at most one `respond` or `else` option",
+                    ));
+                }
+                response = Some(input.parse()?);
+            } else {
+                let key: Expr = input.parse()?;
+                input.parse::<Token![=>]>()?;
+                let value: Expr = input.parse()?;
+                params.push((key, value));
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+        }
+        Ok(MultiRequireValueData { params, response })
+    }
+}
@@ -0,0 +1,21 @@
+use super::*;
+
+/// Required key data container for the `require_header` and `require_query` guards.
+///
+/// Supports both single and multiple keys.
+pub(crate) struct MultiRequireData {
+    /// Vector of required key expressions.
+    pub(crate) keys: Vec<Expr>,
+    /// The optional response sent when the guard stops the request.
+    pub(crate) response: Option<GuardResponse>,
+}
+
+/// Key-value data container for the equality and pattern guards on headers and queries.
+///
+/// Supports both single and multiple key-value pairs.
+pub(crate) struct MultiRequireValueData {
+    /// Vector of key expressions and their expected values or matchers.
+    pub(crate) params: Vec<(Expr, Expr)>,
+    /// The optional response sent when the guard stops the request.
+    pub(crate) response: Option<GuardResponse>,
+}
@@ -0,0 +1,7 @@
+/// Defines how a one-shot response is sent.
+pub(crate) enum RespondSend {
+    /// Sends the response, panicking on failure.
+    Send,
+    /// Tries to send the response, ignoring failures.
+    TrySend,
+}
@@ -0,0 +1,79 @@
+use super::*;
+
+/// Sets the response version, status code, headers and body and sends it in one step.
+///
+/// The parts are expanded in the same order as the individual macros would be written:
+/// `response_version`, `response_status_code`, `response_header`, `response_body` and
+/// `send` or `try_send`.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with the response setting and sending.
+pub(crate) fn respond_macro(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+) -> TokenStream {
+    let respond_data: RespondData = parse_macro_input!(attr as RespondData);
+    let mut warnings: proc_macro2::TokenStream = quote! {};
+    if let Some(status_code) = &respond_data.status_code {
+        match validate_status_code(status_code) {
+            Ok(Some(code)) => warnings.extend(status_code_warning(code, status_code)),
+            Ok(None) => {}
+            Err(err) => return err.to_compile_error().into(),
+        }
+    }
+    let version: Option<proc_macro2::TokenStream> =
+        match respond_data.version.as_ref().map(version_value).transpose() {
+            Ok(version) => version,
+            Err(err) => return err.to_compile_error().into(),
+        };
+    inject(position, item, |context: &Ident, stream: &Ident| {
+        let new_context: proc_macro2::TokenStream = leak_mut_context(false, context);
+        let version: Option<proc_macro2::TokenStream> =
+            version.map(|version: proc_macro2::TokenStream| {
+                quote! {
+                    #new_context.get_mut_response().set_version(#version);
+                }
+            });
+        let status_code: Option<proc_macro2::TokenStream> =
+            respond_data.status_code.as_ref().map(|status_code: &Expr| {
+                quote! {
+                    #new_context.get_mut_response().set_status_code(::hyperlane::ResponseStatusCode::from(#status_code as usize));
+                }
+            });
+        let headers = respond_data.headers.iter().map(|(key, value)| {
+            quote! {
+                #new_context.get_mut_response().set_header(&#key, &#value);
+            }
+        });
+        let body: Option<proc_macro2::TokenStream> =
+            respond_data.body.as_ref().map(|body: &Expr| {
+                quote! {
+                    #new_context.get_mut_response().set_body(&#body);
+                }
+            });
+        let send: Option<proc_macro2::TokenStream> = respond_data.send.as_ref().map(|send: &RespondSend| match send {
+            RespondSend::Send => quote! {
+                #stream.send(#context.get_mut_response().build()).await;
+            },
+            RespondSend::TrySend => quote! {
+                let _: ::std::result::Result<(), ::hyperlane::ResponseError> = #stream.try_send(#context.get_mut_response().build()).await;
+            },
+        });
+        quote! {
+            #warnings
+            #version
+            #status_code
+            #(#headers)*
+            #body
+            #send
+        }
+    })
+}
@@ -0,0 +1,138 @@
+use super::*;
+
+/// Checks whether the next option is a bare `send` or `try_send`.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `bool` - `true` if the next option is `send` or `try_send`.
+fn is_send_option(input: ParseStream) -> bool {
+    let fork: ParseStream = &input.fork();
+    match fork.parse::<Ident>() {
+        Ok(name) => {
+            (name == "send" || name == "try_send") && (fork.is_empty() || fork.peek(Token![,]))
+        }
+        Err(_) => false,
+    }
+}
+
+/// Checks whether the next option is `headers(...)`.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `bool` - `true` if the next option is `headers` followed by parentheses.
+fn is_headers_option(input: ParseStream) -> bool {
+    let fork: ParseStream = &input.fork();
+    match fork.parse::<Ident>() {
+        Ok(name) => name == "headers" && fork.peek(token::Paren),
+        Err(_) => false,
+    }
+}
+
+/// Implementation of Parse trait for RespondData.
+///
+/// Parses a leading status code followed by `version = expr`, `headers(KEY => VALUE, ...)`,
+/// `body = expr` and `send` or `try_send` options in any order. Each option may only
+/// appear once.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<RespondData>` - Parsed RespondData or error.
+impl Parse for RespondData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut data: RespondData = RespondData {
+            version: None,
+            status_code: None,
+            headers: Vec::new(),
+            body: None,
+            send: None,
+        };
+        let mut has_headers: bool = false;
+        while !input.is_empty() {
+            let is_option: bool = input.peek(Ident) && !input.peek2(Token![::]);
+            if is_option && input.peek2(Token![=]) {
+                let name: Ident = input.parse()?;
+                input.parse::<Token![=]>()?;
+                let value: Expr = input.parse()?;
+                let slot: &mut Option<Expr> = if name == "status" {
+                    &mut data.status_code
+                } else if name == "version" {
+                    &mut data.version
+                } else if name == "body" {
+                    &mut data.body
+                } else {
+                    return Err(syn::Error::new_spanned(
+                        &name,
+                        "expected `status`, `version` or `body`",
+                    ));
+                };
+                if slot.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        &name,
+                        format!("`{name}` is specified more than once"),
+                    ));
+                }
+                *slot = Some(value);
+            } else if is_headers_option(input) {
+                let name: Ident = input.parse()?;
+                if has_headers {
+                    return Err(syn::Error::new_spanned(
+                        &name,
+                        "`headers` is specified more than once",
+                    ));
+                }
+                has_headers = true;
+                let content;
+                parenthesized!(content in input);
+                while !content.is_empty() {
+                    let key: Expr = content.parse()?;
+                    content.parse::<Token![=>]>()?;
+                    let value: Expr = content.parse()?;
+                    data.headers.push((key, value));
+                    if content.is_empty() {
+                        break;
+                    }
+                    content.parse::<Token![,]>()?;
+                }
+            } else if is_option && is_send_option(input) {
+                let name: Ident = input.parse()?;
+                if data.send.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        &name,
+                        "only one of `send` or `try_send` can be specified",
+                    ));
+                }
+                data.send = Some(if name == "send" {
+                    RespondSend::Send
+                } else {
+                    RespondSend::TrySend
+                });
+            } else {
+                let value: Expr = input.parse()?;
+                if data.status_code.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        &value,
+                        "status code is specified more than once",
+                    ));
+                }
+                data.status_code = Some(value);
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        Ok(data)
+    }
+}
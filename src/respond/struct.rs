@@ -0,0 +1,17 @@
+use super::*;
+
+/// Represents data for a one-shot response.
+///
+/// This struct holds every part of the response that should be set, in expansion order.
+pub(crate) struct RespondData {
+    /// The optional response version.
+    pub(crate) version: Option<Expr>,
+    /// The optional response status code.
+    pub(crate) status_code: Option<Expr>,
+    /// The response headers to set, as key-value pairs.
+    pub(crate) headers: Vec<(Expr, Expr)>,
+    /// The optional response body.
+    pub(crate) body: Option<Expr>,
+    /// How the response is sent, if at all.
+    pub(crate) send: Option<RespondSend>,
+}
@@ -1,7 +1,135 @@
 use super::*;
 
+/// Validates a literal status code.
+///
+/// Literals must be within `100..=599`. Expressions that are not integer literals are
+/// left to be checked at runtime.
+///
+/// # Arguments
+///
+/// - `&Expr` - The status code expression.
+///
+/// # Returns
+///
+/// - `syn::Result<Option<usize>>` - The literal status code, `None` for non-literal expressions,
+///   or an error for out of range literals.
+pub(crate) fn validate_status_code(value: &Expr) -> syn::Result<Option<usize>> {
+    let (lit_int, negative): (&LitInt, bool) = match value {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit_int),
+            ..
+        }) => (lit_int, false),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => match &**expr {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(lit_int),
+                ..
+            }) => (lit_int, true),
+            _ => return Ok(None),
+        },
+        _ => return Ok(None),
+    };
+    match lit_int.base10_parse::<usize>() {
+        Ok(code) if !negative && (100..=599).contains(&code) => Ok(Some(code)),
+        _ => Err(syn::Error::new_spanned(
+            value,
+            "status code must be between 100 and 599",
+        )),
+    }
+}
+
+/// Generates a warning for a non-standard literal status code.
+///
+/// # Arguments
+///
+/// - `usize` - The status code.
+/// - `&Expr` - The status code expression, used for the warning span.
+///
+/// # Returns
+///
+/// - `TokenStream2` - A warning when the code has no standard reason phrase,
+///   or an empty token stream otherwise.
+pub(crate) fn status_code_warning(code: usize, value: &Expr) -> proc_macro2::TokenStream {
+    if ::hyperlane::HttpStatus::phrase(code) != ::hyperlane::HttpStatus::Unknown.to_string() {
+        return quote! {};
+    }
+    compile_warning(
+        value.span(),
+        &format!("non-standard HTTP status code {code}"),
+    )
+}
+
+/// Converts a response version expression into an `HttpVersion` expression.
+///
+/// String literals are parsed at compile time into the matching `HttpVersion` variant.
+/// Other expressions are passed through unchanged.
+///
+/// # Arguments
+///
+/// - `&Expr` - The version expression.
+///
+/// # Returns
+///
+/// - `syn::Result<TokenStream2>` - The version expression, or an error for unknown literal versions.
+pub(crate) fn version_value(value: &Expr) -> syn::Result<proc_macro2::TokenStream> {
+    let version: proc_macro2::TokenStream = match value {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit_str),
+            ..
+        }) => match lit_str.value().parse::<::hyperlane::HttpVersion>() {
+            Ok(::hyperlane::HttpVersion::Http0_9) => quote! { ::hyperlane::HttpVersion::Http0_9 },
+            Ok(::hyperlane::HttpVersion::Http1_0) => quote! { ::hyperlane::HttpVersion::Http1_0 },
+            Ok(::hyperlane::HttpVersion::Http1_1) => quote! { ::hyperlane::HttpVersion::Http1_1 },
+            Ok(::hyperlane::HttpVersion::Http2) => quote! { ::hyperlane::HttpVersion::Http2 },
+            Ok(::hyperlane::HttpVersion::Http3) => quote! { ::hyperlane::HttpVersion::Http3 },
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    lit_str,
+                    "unknown HTTP version, expected one of `HTTP/0.9`, `HTTP/1.0`, `HTTP/1.1`, `HTTP/2` or `HTTP/3`",
+                ));
+            }
+        },
+        _ => quote! { #value },
+    };
+    Ok(version)
+}
+
+/// Checks a literal reason phrase against a literal status code.
+///
+/// # Arguments
+///
+/// - `usize` - The status code.
+/// - `&LitStr` - The reason phrase literal.
+///
+/// # Returns
+///
+/// - `TokenStream2` - A warning when the phrase differs from the standard phrase of the code,
+///   or an empty token stream otherwise.
+fn check_reason_phrase(code: usize, phrase: &LitStr) -> proc_macro2::TokenStream {
+    let standard: String = ::hyperlane::HttpStatus::phrase(code);
+    if standard == ::hyperlane::HttpStatus::Unknown.to_string()
+        || standard.eq_ignore_ascii_case(&phrase.value())
+    {
+        return quote! {};
+    }
+    compile_warning(
+        phrase.span(),
+        &format!(
+            "reason phrase `{}` does not match status code {code}, expected `{standard}`",
+            phrase.value()
+        ),
+    )
+}
+
 /// Sets response status code from macro input.
 ///
+/// Literal status codes are validated at compile time: values outside `100..=599` are
+/// rejected and non-standard codes produce a warning. A literal `response_reason_phrase`
+/// on the same function is checked against the code.
+///
 /// # Arguments
 ///
 /// - `TokenStream` - The attribute token stream.
@@ -20,9 +148,22 @@ pub(crate) fn response_status_code_macro(
         Ok(v) => v,
         Err(err) => return err.to_compile_error().into(),
     };
+    let mut warnings: proc_macro2::TokenStream = quote! {};
+    match validate_status_code(&value) {
+        Ok(Some(code)) => {
+            warnings.extend(status_code_warning(code, &value));
+            if let Some(Lit::Str(phrase)) = find_attribute_literal(&item, "response_reason_phrase")
+            {
+                warnings.extend(check_reason_phrase(code, &phrase));
+            }
+        }
+        Ok(None) => {}
+        Err(err) => return err.to_compile_error().into(),
+    }
     inject(position, item, |context: &Ident, _: &Ident| {
         let new_context: proc_macro2::TokenStream = leak_mut_context(false, context);
         quote! {
+            #warnings
             #new_context.get_mut_response().set_status_code(::hyperlane::ResponseStatusCode::from(#value as usize));
         }
     })
@@ -30,6 +171,8 @@ pub(crate) fn response_status_code_macro(
 
 /// Sets response reason phrase from macro input.
 ///
+/// A literal phrase is checked against a literal `response_status_code` on the same function.
+///
 /// # Arguments
 ///
 /// - `TokenStream` - The attribute token stream.
@@ -48,9 +191,20 @@ pub(crate) fn response_reason_phrase_macro(
         Ok(v) => v,
         Err(err) => return err.to_compile_error().into(),
     };
+    let mut warnings: proc_macro2::TokenStream = quote! {};
+    if let Expr::Lit(ExprLit {
+        lit: Lit::Str(phrase),
+        ..
+    }) = &value
+        && let Some(Lit::Int(code)) = find_attribute_literal(&item, "response_status_code")
+        && let Ok(code) = code.base10_parse::<usize>()
+    {
+        warnings.extend(check_reason_phrase(code, phrase));
+    }
     inject(position, item, |context: &Ident, _: &Ident| {
         let new_context: proc_macro2::TokenStream = leak_mut_context(false, context);
         quote! {
+            #warnings
             #new_context.get_mut_response().set_reason_phrase(&#value);
         }
     })
@@ -93,6 +247,67 @@ pub(crate) fn response_header_macro(
     })
 }
 
+/// Removes response headers from macro input.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with header removal.
+pub(crate) fn remove_response_header_macro(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+) -> TokenStream {
+    let header_data: RemoveResponseHeaderData =
+        parse_macro_input!(attr as RemoveResponseHeaderData);
+    let keys: Vec<Expr> = header_data.keys;
+    inject(position, item, |context: &Ident, _: &Ident| {
+        let new_context: proc_macro2::TokenStream = leak_mut_context(false, context);
+        quote! {
+            #(#new_context.get_mut_response().remove_header(&#keys);)*
+        }
+    })
+}
+
+/// Sets response header from macro input only if it is not already present.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with conditional header setting.
+pub(crate) fn response_header_if_absent_macro(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+) -> TokenStream {
+    let header_data: ResponseHeaderIfAbsentData =
+        parse_macro_input!(attr as ResponseHeaderIfAbsentData);
+    let key: Expr = header_data.key;
+    let value: Expr = header_data.value;
+    inject(position, item, |context: &Ident, _: &Ident| {
+        let new_context: proc_macro2::TokenStream = leak_mut_context(false, context);
+        quote! {
+            {
+                let key = &#key;
+                let response: &mut ::hyperlane::Response = #new_context.get_mut_response();
+                if !response.has_header(key) {
+                    response.set_header(key, &#value);
+                }
+            }
+        }
+    })
+}
+
 /// Sets response body from macro input.
 ///
 /// # Arguments
@@ -141,6 +356,8 @@ pub(crate) fn clear_response_headers_macro(item: TokenStream, position: Position
 
 /// Sets response version from macro input.
 ///
+/// A string literal is validated at compile time and must name a known HTTP version.
+///
 /// # Arguments
 ///
 /// - `TokenStream` - The attribute token stream.
@@ -159,10 +376,14 @@ pub(crate) fn response_version_macro(
         Ok(v) => v,
         Err(err) => return err.to_compile_error().into(),
     };
+    let version: proc_macro2::TokenStream = match version_value(&value) {
+        Ok(version) => version,
+        Err(err) => return err.to_compile_error().into(),
+    };
     inject(position, item, |context: &Ident, _: &Ident| {
         let new_context: proc_macro2::TokenStream = leak_mut_context(false, context);
         quote! {
-            #new_context.get_mut_response().set_version(#value);
+            #new_context.get_mut_response().set_version(#version);
         }
     })
 }
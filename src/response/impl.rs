@@ -27,6 +27,36 @@ impl Parse for ResponseHeaderData {
     }
 }
 
+/// Implementation of Parse trait for RemoveResponseHeaderData.
+///
+/// Parses comma-separated header keys from input stream.
+impl Parse for RemoveResponseHeaderData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let keys: Punctuated<Expr, Token![,]> = Punctuated::parse_terminated(input)?;
+        if keys.is_empty() {
+            return Err(syn::Error::new(
+                input.span(),
+                "Expected at least one header key",
+            ));
+        }
+        Ok(RemoveResponseHeaderData {
+            keys: keys.into_iter().collect(),
+        })
+    }
+}
+
+/// Implementation of Parse trait for ResponseHeaderIfAbsentData.
+///
+/// Parses header key and value separated by `=>` from input stream.
+impl Parse for ResponseHeaderIfAbsentData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Expr = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let value: Expr = input.parse()?;
+        Ok(ResponseHeaderIfAbsentData { key, value })
+    }
+}
+
 /// Implementation of Parse trait for ResponseBodyData.
 ///
 /// Parses response body expression from input stream.
@@ -12,6 +12,24 @@ pub(crate) struct ResponseHeaderData {
     pub(crate) operation: HeaderOperation,
 }
 
+/// Represents data for removing response headers.
+///
+/// This struct holds the keys of the headers to remove.
+pub(crate) struct RemoveResponseHeaderData {
+    /// The header keys.
+    pub(crate) keys: Vec<Expr>,
+}
+
+/// Represents data for a response header set only when absent.
+///
+/// This struct holds the key and value for the response header.
+pub(crate) struct ResponseHeaderIfAbsentData {
+    /// The header key.
+    pub(crate) key: Expr,
+    /// The header value.
+    pub(crate) value: Expr,
+}
+
 /// Represents data for a response body.
 ///
 /// This struct holds the expression for the response body.
@@ -0,0 +1,7 @@
+/// Defines how cookie values are protected by a declared cookie key.
+pub(crate) enum CookieKeyMode {
+    /// Values are sent in clear text behind an HMAC-SHA256 tag.
+    Signed,
+    /// Values are encrypted with AES-256-GCM, so the client cannot read them.
+    Encrypted,
+}
@@ -0,0 +1,167 @@
+use super::*;
+
+/// Generates the type of a cookie key static.
+///
+/// The key is a pair of closures: the first encodes a cookie value for a name,
+/// the second decodes it and returns `None` when the value was tampered with.
+///
+/// # Returns
+///
+/// - `TokenStream2` - The codec tuple type.
+fn cookie_codec_type() -> proc_macro2::TokenStream {
+    quote! {
+        (
+            ::std::boxed::Box<dyn Fn(&str, &str) -> ::std::string::String + Send + Sync>,
+            ::std::boxed::Box<dyn Fn(&str, &str) -> ::std::option::Option<::std::string::String> + Send + Sync>,
+        )
+    }
+}
+
+/// Declares a cookie key for the signed cookie macros.
+///
+/// The key is a lazily initialized static built from at least 64 bytes of key material
+/// with the `cookie` crate. Signed values carry an HMAC-SHA256 tag over the cookie name and
+/// value. Encrypted values are sealed with AES-256-GCM under a random 96-bit nonce, with
+/// the cookie name as associated data. A key literal that is too short is rejected here,
+/// any other key when the static is first used.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The optional visibility, the key name and the key expression.
+/// - `CookieKeyMode` - How cookie values are protected with the key.
+///
+/// # Returns
+///
+/// - `TokenStream` - The key static.
+pub(crate) fn cookie_key_macro(input: TokenStream, mode: CookieKeyMode) -> TokenStream {
+    let CookieKeyData { vis, name, key } = parse_macro_input!(input as CookieKeyData);
+    let codec_type: proc_macro2::TokenStream = cookie_codec_type();
+    let message: String = format!("invalid cookie key `{name}`: {{error}}");
+    let (encode_jar, decode_jar, decode): (Ident, Ident, Ident) = match mode {
+        CookieKeyMode::Signed => (
+            Ident::new("signed_mut", Span::call_site()),
+            Ident::new("signed", Span::call_site()),
+            Ident::new("verify", Span::call_site()),
+        ),
+        CookieKeyMode::Encrypted => (
+            Ident::new("private_mut", Span::call_site()),
+            Ident::new("private", Span::call_site()),
+            Ident::new("decrypt", Span::call_site()),
+        ),
+    };
+    quote! {
+        #vis static #name: ::std::sync::LazyLock<#codec_type> = ::std::sync::LazyLock::new(|| {
+            let encode_key: ::cookie::Key = ::cookie::Key::try_from(::std::convert::AsRef::<[u8]>::as_ref(&#key))
+                .unwrap_or_else(|error| panic!(#message));
+            let decode_key: ::cookie::Key = encode_key.clone();
+            (
+                ::std::boxed::Box::new(move |name: &str, value: &str| -> ::std::string::String {
+                    let mut jar: ::cookie::CookieJar = ::cookie::CookieJar::new();
+                    jar.#encode_jar(&encode_key)
+                        .add(::cookie::Cookie::new(name.to_owned(), value.to_owned()));
+                    jar.get(name)
+                        .map(|cookie: &::cookie::Cookie<'static>| cookie.value().to_owned())
+                        .unwrap_or_default()
+                }),
+                ::std::boxed::Box::new(move |name: &str, data: &str| -> ::std::option::Option<::std::string::String> {
+                    ::cookie::CookieJar::new()
+                        .#decode_jar(&decode_key)
+                        .#decode(::cookie::Cookie::new(name.to_owned(), data.to_owned()))
+                        .map(|cookie: ::cookie::Cookie<'static>| cookie.value().to_owned())
+                }),
+            )
+        });
+    }
+    .into()
+}
+
+/// Gets request cookies protected by a declared key and assigns them to specified variables.
+/// Supports both single and multiple cookie extraction.
+///
+/// Missing, unsigned or tampered cookies are bound as `None`.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with verified cookie extraction.
+pub(crate) fn request_signed_cookie_macro(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+) -> TokenStream {
+    let multi_cookie: MultiSignedCookieData = parse_macro_input!(attr as MultiSignedCookieData);
+    let key: &Path = &multi_cookie.key;
+    inject(position, item, |context: &Ident, _: &Ident| {
+        let statements = multi_cookie.params.iter().map(|(key_name, variable)| {
+            quote! {
+                let #variable: Option<::hyperlane::CookieValue> = {
+                    let __hyperlane_cookie_name = #key_name;
+                    let __hyperlane_cookie_name: &str =
+                        ::std::convert::AsRef::<str>::as_ref(&__hyperlane_cookie_name);
+                    let (_, __hyperlane_decode) = &*#key;
+                    #context
+                        .get_request()
+                        .try_get_cookie(__hyperlane_cookie_name)
+                        .and_then(|__hyperlane_cookie_value: ::hyperlane::CookieValue| {
+                            __hyperlane_decode(__hyperlane_cookie_name, &__hyperlane_cookie_value)
+                        })
+                };
+            }
+        });
+        quote! {
+            #(#statements)*
+        }
+    })
+}
+
+/// Signs or encrypts cookie values with a declared key and adds them to the response as
+/// `Set-Cookie` headers. Supports both single and multiple cookies.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with signed cookie setting.
+pub(crate) fn response_signed_cookie_macro(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+) -> TokenStream {
+    let multi_cookie: MultiSignedCookieValueData =
+        parse_macro_input!(attr as MultiSignedCookieValueData);
+    let key: &Path = &multi_cookie.key;
+    inject(position, item, |context: &Ident, _: &Ident| {
+        let new_context: proc_macro2::TokenStream = leak_mut_context(false, context);
+        let statements = multi_cookie.params.iter().map(|(key_name, value)| {
+            quote! {
+                {
+                    let __hyperlane_cookie_name = #key_name;
+                    let __hyperlane_cookie_value = #value;
+                    let __hyperlane_cookie_name: &str =
+                        ::std::convert::AsRef::<str>::as_ref(&__hyperlane_cookie_name);
+                    let (__hyperlane_encode, _) = &*#key;
+                    let __hyperlane_cookie_value: ::std::string::String = __hyperlane_encode(
+                        __hyperlane_cookie_name,
+                        ::std::convert::AsRef::<str>::as_ref(&__hyperlane_cookie_value),
+                    );
+                    #new_context.get_mut_response().add_header(
+                        ::hyperlane::SET_COOKIE,
+                        ::hyperlane::CookieBuilder::new(__hyperlane_cookie_name, __hyperlane_cookie_value).build(),
+                    );
+                }
+            }
+        });
+        quote! {
+            #(#statements)*
+        }
+    })
+}
@@ -0,0 +1,94 @@
+use super::*;
+
+/// Implementation of Parse trait for CookieKeyData.
+///
+/// Parses an optional visibility, the key name and `= key`.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<CookieKeyData>` - Parsed CookieKeyData or error.
+impl Parse for CookieKeyData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis: Visibility = input.parse()?;
+        let name: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let key: Expr = input.parse()?;
+        input.parse::<Option<Token![;]>>()?;
+        let size: Option<usize> = match &key {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(lit), ..
+            }) => Some(lit.value().len()),
+            Expr::Lit(ExprLit {
+                lit: Lit::ByteStr(lit),
+                ..
+            }) => Some(lit.value().len()),
+            _ => None,
+        };
+        if size.is_some_and(|size: usize| size < COOKIE_KEY_MIN_SIZE) {
+            return Err(syn::Error::new_spanned(
+                &key,
+                format!("cookie key must be at least {COOKIE_KEY_MIN_SIZE} bytes"),
+            ));
+        }
+        Ok(CookieKeyData { vis, name, key })
+    }
+}
+
+/// Implementation of Parse trait for MultiSignedCookieData.
+///
+/// Parses the key path followed by cookie name-variable pairs.
+/// Supports both single and multiple pairs.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<MultiSignedCookieData>` - Parsed MultiSignedCookieData or error.
+impl Parse for MultiSignedCookieData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Path = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let MultiCookieData { params } = input.parse()?;
+        Ok(MultiSignedCookieData { key, params })
+    }
+}
+
+/// Implementation of Parse trait for MultiSignedCookieValueData.
+///
+/// Parses the key path followed by cookie name-value pairs.
+/// Supports both single and multiple pairs.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<MultiSignedCookieValueData>` - Parsed MultiSignedCookieValueData or error.
+impl Parse for MultiSignedCookieValueData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Path = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let mut params: Vec<(Expr, Expr)> = Vec::new();
+        loop {
+            let name: Expr = input.parse()?;
+            input.parse::<Token![=>]>()?;
+            let value: Expr = input.parse()?;
+            params.push((name, value));
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+        }
+        Ok(MultiSignedCookieValueData { key, params })
+    }
+}
@@ -0,0 +1,35 @@
+use super::*;
+
+/// Cookie key declaration for the `signed_cookie_key` and `encrypted_cookie_key` macros.
+///
+/// Stores the visibility and the name of the generated key static with the key expression.
+pub(crate) struct CookieKeyData {
+    /// The visibility of the key static.
+    pub(crate) vis: Visibility,
+    /// The name of the key static.
+    pub(crate) name: Ident,
+    /// The key expression, anything implementing `AsRef<[u8]>`.
+    pub(crate) key: Expr,
+}
+
+/// Container for signed request cookies data.
+///
+/// Stores the cookie key followed by the parsed cookie name-variable pairs.
+/// Supports both single and multiple pairs.
+pub(crate) struct MultiSignedCookieData {
+    /// The path of the key static declared with a cookie key macro.
+    pub(crate) key: Path,
+    /// Vector of cookie name-variable pairs.
+    pub(crate) params: Vec<(Expr, Ident)>,
+}
+
+/// Container for signed response cookies data.
+///
+/// Stores the cookie key followed by the parsed cookie name-value pairs.
+/// Supports both single and multiple pairs.
+pub(crate) struct MultiSignedCookieValueData {
+    /// The path of the key static declared with a cookie key macro.
+    pub(crate) key: Path,
+    /// Vector of cookie name-value pairs.
+    pub(crate) params: Vec<(Expr, Expr)>,
+}
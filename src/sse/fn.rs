@@ -0,0 +1,192 @@
+use super::*;
+
+/// Generates code appending a field to the event being built, one line per value line.
+///
+/// Values are converted with `ToString` and split on `\r\n`, `\r` and `\n`, so multi-line
+/// data becomes consecutive `data:` lines.
+///
+/// # Arguments
+///
+/// - `&str` - The field prefix, such as `"data: "`.
+/// - `&Expr` - The field value.
+///
+/// # Returns
+///
+/// - `TokenStream2` - The block appending the field to `sse_frame`.
+fn sse_multi_line_field(prefix: &str, value: &Expr) -> proc_macro2::TokenStream {
+    quote! {
+        {
+            let value: ::std::string::String = ::std::string::ToString::to_string(&#value);
+            for line in value.split("\r\n").flat_map(|line: &str| line.split(['\r', '\n'])) {
+                sse_frame.push_str(#prefix);
+                sse_frame.push_str(line);
+                sse_frame.push('\n');
+            }
+        }
+    }
+}
+
+/// Generates code appending a single-line field to the event being built.
+///
+/// Values are converted with `ToString`, and line breaks and NUL characters, which cannot
+/// appear in these fields, are removed.
+///
+/// # Arguments
+///
+/// - `&str` - The field prefix, such as `"event: "`.
+/// - `&Expr` - The field value.
+///
+/// # Returns
+///
+/// - `TokenStream2` - The block appending the field to `sse_frame`.
+fn sse_single_line_field(prefix: &str, value: &Expr) -> proc_macro2::TokenStream {
+    quote! {
+        {
+            let value: ::std::string::String = ::std::string::ToString::to_string(&#value);
+            sse_frame.push_str(#prefix);
+            sse_frame.extend(value.chars().filter(|character: &char| !matches!(character, '\r' | '\n' | '\0')));
+            sse_frame.push('\n');
+        }
+    }
+}
+
+/// Starts a Server-Sent Events response.
+///
+/// Sets `Content-Type: text/event-stream`, `Cache-Control: no-cache` and
+/// `Connection: keep-alive`, clears the body, then sends and flushes the response head.
+/// The function returns `Status::Reject` when the head cannot be sent.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream sending the event stream head.
+pub(crate) fn sse_macro(item: TokenStream, position: Position) -> TokenStream {
+    inject(position, item, |context: &Ident, stream: &Ident| {
+        let new_context: proc_macro2::TokenStream = leak_mut_context(false, context);
+        quote! {
+            {
+                let response: &mut ::hyperlane::Response = #new_context.get_mut_response();
+                response
+                    .set_header(::hyperlane::CONTENT_TYPE, ::hyperlane::TEXT_EVENT_STREAM)
+                    .set_header(#CACHE_CONTROL_HEADER, ::hyperlane::NO_CACHE)
+                    .set_header(::hyperlane::CONNECTION, ::hyperlane::KEEP_ALIVE)
+                    .set_body(::hyperlane::ResponseBody::new());
+                if #stream.try_send(response.build()).await.is_err() || #stream.try_flush().await.is_err() {
+                    return ::hyperlane::Status::Reject;
+                }
+            }
+        }
+    })
+}
+
+/// Sends one Server-Sent Events frame.
+///
+/// The frame holds the comment, `event`, `id`, `retry` and `data` fields that were given,
+/// in that order, followed by a blank line. It is sent and flushed, and the expression
+/// evaluates to the `Result<(), ResponseError>` of doing so.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The stream and the event fields.
+///
+/// # Returns
+///
+/// - `TokenStream` - The send expression.
+pub(crate) fn sse_send_macro(input: TokenStream) -> TokenStream {
+    let SseSendData {
+        stream,
+        comment,
+        event,
+        id,
+        retry,
+        data,
+    } = parse_macro_input!(input as SseSendData);
+    let comment: Option<proc_macro2::TokenStream> = comment
+        .as_ref()
+        .map(|comment: &Expr| sse_multi_line_field(": ", comment));
+    let event: Option<proc_macro2::TokenStream> = event
+        .as_ref()
+        .map(|event: &Expr| sse_single_line_field("event: ", event));
+    let id: Option<proc_macro2::TokenStream> = id
+        .as_ref()
+        .map(|id: &Expr| sse_single_line_field("id: ", id));
+    let retry: Option<proc_macro2::TokenStream> = retry.as_ref().map(|retry: &Expr| {
+        quote! {
+            {
+                let retry: u64 = #retry;
+                sse_frame.push_str("retry: ");
+                sse_frame.push_str(&retry.to_string());
+                sse_frame.push('\n');
+            }
+        }
+    });
+    let data: Option<proc_macro2::TokenStream> = data
+        .as_ref()
+        .map(|data: &Expr| sse_multi_line_field("data: ", data));
+    quote! {
+        {
+            let sse_stream: &mut ::hyperlane::Stream = #stream;
+            let mut sse_frame: ::std::string::String = ::std::string::String::new();
+            #comment
+            #event
+            #id
+            #retry
+            #data
+            sse_frame.push('\n');
+            match sse_stream.try_send(sse_frame.into_bytes()).await {
+                Ok(()) => sse_stream.try_flush().await,
+                Err(error) => Err(error),
+            }
+        }
+    }
+    .into()
+}
+
+/// Awaits a future while keeping a Server-Sent Events stream alive.
+///
+/// Whenever the future has not completed for the keep-alive interval, a comment frame is sent
+/// and flushed so proxies and clients do not treat the stream as idle. The expression evaluates
+/// to `Ok` with the future's output, or to the `ResponseError` of a failed keep-alive once the
+/// client has gone away.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The stream, the future and the optional `keep_alive` option.
+///
+/// # Returns
+///
+/// - `TokenStream` - The wait expression.
+pub(crate) fn sse_wait_macro(input: TokenStream) -> TokenStream {
+    let SseWaitData {
+        stream,
+        future,
+        keep_alive_millis,
+    } = parse_macro_input!(input as SseWaitData);
+    quote! {
+        {
+            let sse_stream: &mut ::hyperlane::Stream = #stream;
+            let keep_alive: ::std::time::Duration = ::std::time::Duration::from_millis(#keep_alive_millis);
+            let mut sse_future = ::std::pin::pin!(#future);
+            loop {
+                match ::hyperlane::tokio::time::timeout(keep_alive, &mut sse_future).await {
+                    Ok(output) => break Ok(output),
+                    Err(_) => {
+                        let sent: ::std::result::Result<(), ::hyperlane::ResponseError> =
+                            match sse_stream.try_send(#SSE_KEEP_ALIVE_COMMENT.as_bytes()).await {
+                                Ok(()) => sse_stream.try_flush().await,
+                                Err(error) => Err(error),
+                            };
+                        if let Err(error) = sent {
+                            break Err(error);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    .into()
+}
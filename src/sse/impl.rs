@@ -0,0 +1,114 @@
+use super::*;
+
+/// Implementation of Parse trait for SseSendData.
+///
+/// Parses the stream followed by `comment`, `event`, `id`, `retry` and `data` options in any
+/// order, at least one of which must be given.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<SseSendData>` - Parsed SseSendData or error.
+impl Parse for SseSendData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let stream: Expr = input.parse()?;
+        let mut data: SseSendData = SseSendData {
+            stream,
+            comment: None,
+            event: None,
+            id: None,
+            retry: None,
+            data: None,
+        };
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let name: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let value: Expr = input.parse()?;
+            let field: &mut Option<Expr> = if name == SSE_COMMENT_OPTION {
+                &mut data.comment
+            } else if name == SSE_EVENT_OPTION {
+                &mut data.event
+            } else if name == SSE_ID_OPTION {
+                &mut data.id
+            } else if name == SSE_RETRY_OPTION {
+                &mut data.retry
+            } else if name == SSE_DATA_OPTION {
+                &mut data.data
+            } else {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "expected `comment`, `event`, `id`, `retry` or `data`",
+                ));
+            };
+            if field.is_some() {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("expected at most one `{name}` option"),
+                ));
+            }
+            *field = Some(value);
+        }
+        if data.comment.is_none()
+            && data.event.is_none()
+            && data.id.is_none()
+            && data.retry.is_none()
+            && data.data.is_none()
+        {
+            return Err(
+                input.error("expected at least one of `comment`, `event`, `id`, `retry` or `data`")
+            );
+        }
+        Ok(data)
+    }
+}
+
+/// Implementation of Parse trait for SseWaitData.
+///
+/// Parses the stream and the future followed by an optional `keep_alive = "15s"` option.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<SseWaitData>` - Parsed SseWaitData or error.
+impl Parse for SseWaitData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let stream: Expr = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let future: Expr = input.parse()?;
+        let mut keep_alive_millis: u64 = SSE_DEFAULT_KEEP_ALIVE_MILLIS;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let name: Ident = input.parse()?;
+            if name != SSE_KEEP_ALIVE_OPTION {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "expected `keep_alive = \"15s\"`",
+                ));
+            }
+            input.parse::<Token![=]>()?;
+            let duration: LitStr = input.parse()?;
+            keep_alive_millis = parse_duration_millis(&duration)?;
+            if keep_alive_millis == 0 {
+                return Err(syn::Error::new(
+                    duration.span(),
+                    "expected a keep-alive interval greater than zero",
+                ));
+            }
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(SseWaitData {
+            stream,
+            future,
+            keep_alive_millis,
+        })
+    }
+}
@@ -0,0 +1,31 @@
+use super::*;
+
+/// Event fields for the `sse_send` macro.
+///
+/// Stores the stream and each optional field of the event.
+pub(crate) struct SseSendData {
+    /// The stream expression.
+    pub(crate) stream: Expr,
+    /// The optional comment, sent before the fields.
+    pub(crate) comment: Option<Expr>,
+    /// The optional event type.
+    pub(crate) event: Option<Expr>,
+    /// The optional event id.
+    pub(crate) id: Option<Expr>,
+    /// The optional reconnection time in milliseconds.
+    pub(crate) retry: Option<Expr>,
+    /// The optional event data.
+    pub(crate) data: Option<Expr>,
+}
+
+/// Arguments for the `sse_wait` macro.
+///
+/// Stores the stream, the awaited future and the keep-alive interval.
+pub(crate) struct SseWaitData {
+    /// The stream expression.
+    pub(crate) stream: Expr,
+    /// The future to await.
+    pub(crate) future: Expr,
+    /// The time without events after which a keep-alive comment is sent, in milliseconds.
+    pub(crate) keep_alive_millis: u64,
+}
@@ -1,9 +1,218 @@
 use super::*;
 
+/// Generates an `Option<Duration>` expression from optional milliseconds.
+///
+/// # Arguments
+///
+/// - `Option<u64>` - The optional duration in milliseconds.
+///
+/// # Returns
+///
+/// - `TokenStream2` - A `Some(Duration)` or `None` expression.
+fn optional_duration(millis: Option<u64>) -> proc_macro2::TokenStream {
+    match millis {
+        Some(millis) => quote! {
+            ::std::option::Option::Some(::std::time::Duration::from_millis(#millis))
+        },
+        None => quote! {
+            ::std::option::Option::None
+        },
+    }
+}
+
+/// Generates the runtime helper waiting for one WebSocket message with heartbeats.
+///
+/// `read_websocket_message` reads the message with `Stream::try_get_websocket_request`. Until
+/// the client sends data it only peeks at the socket, so sending a ping every `ping_interval`
+/// or frames queued on the room `outbox` never interrupts a message being received. Pings and
+/// pongs waiting ahead of a message are consumed, and once no frame arrived for `idle_timeout`
+/// it sends a close frame, closes the stream and fails with `RequestError::ReadTimeout`.
+///
+/// # Returns
+///
+/// - `TokenStream2` - The helper function definition.
+fn websocket_reader_helper() -> proc_macro2::TokenStream {
+    quote! {
+        async fn read_websocket_message(
+            stream: &mut ::hyperlane::Stream,
+            ping_interval: ::std::option::Option<::std::time::Duration>,
+            idle_timeout: ::std::option::Option<::std::time::Duration>,
+            mut outbox: ::std::option::Option<&mut ::hyperlane::tokio::sync::mpsc::Receiver<::std::vec::Vec<::hyperlane::ResponseBody>>>,
+        ) -> ::std::result::Result<::hyperlane::RequestBody, ::hyperlane::RequestError> {
+            enum Event {
+                Peeked(::std::io::Result<usize>),
+                Outbox(::std::vec::Vec<::hyperlane::ResponseBody>),
+                Timer,
+            }
+            let start: ::hyperlane::tokio::time::Instant = ::hyperlane::tokio::time::Instant::now();
+            let mut idle_deadline: ::std::option::Option<::hyperlane::tokio::time::Instant> =
+                idle_timeout.map(|timeout: ::std::time::Duration| start + timeout);
+            let mut next_ping: ::std::option::Option<::hyperlane::tokio::time::Instant> =
+                ping_interval.map(|interval: ::std::time::Duration| start + interval);
+            let mut peeked: ::std::vec::Vec<u8> = vec![0; stream.get_request_config().get_buffer_size().max(1)];
+            loop {
+                let wake: ::std::option::Option<::hyperlane::tokio::time::Instant> = match (next_ping, idle_deadline) {
+                    (::std::option::Option::Some(ping), ::std::option::Option::Some(idle)) => ::std::option::Option::Some(ping.min(idle)),
+                    (ping, idle) => ping.or(idle),
+                };
+                let event: Event = ::hyperlane::tokio::select! {
+                    peeked_len = stream.get_stream().peek(&mut peeked) => Event::Peeked(peeked_len),
+                    ::std::option::Option::Some(frame_list) = async {
+                        match outbox.as_deref_mut() {
+                            ::std::option::Option::Some(outbox) => outbox.recv().await,
+                            ::std::option::Option::None => ::std::option::Option::None,
+                        }
+                    } => Event::Outbox(frame_list),
+                    _ = ::hyperlane::tokio::time::sleep_until(wake.unwrap_or_else(::hyperlane::tokio::time::Instant::now)), if wake.is_some() => Event::Timer,
+                };
+                match event {
+                    Event::Peeked(peeked_len) => {
+                        let peeked_len: usize = peeked_len?;
+                        let mut control_len: usize = 0;
+                        while let ::std::option::Option::Some((frame, consumed)) =
+                            ::hyperlane::WebSocketFrame::decode_ws_frame(&peeked[control_len..peeked_len])
+                        {
+                            if !frame.get_opcode().is_ping() && !frame.get_opcode().is_pong() {
+                                break;
+                            }
+                            control_len += consumed;
+                        }
+                        if control_len == 0 {
+                            let read = stream.try_get_websocket_request();
+                            return match idle_deadline {
+                                ::std::option::Option::Some(deadline) => match ::hyperlane::tokio::time::timeout_at(deadline, read).await {
+                                    Ok(result) => result,
+                                    Err(_) => {
+                                        let _: ::std::result::Result<(), ::hyperlane::ResponseError> = stream.try_send(::hyperlane::CLOSE_FRAME).await;
+                                        stream.set_closed(true);
+                                        Err(::hyperlane::RequestError::ReadTimeout(::hyperlane::HttpStatus::RequestTimeout))
+                                    }
+                                },
+                                ::std::option::Option::None => read.await,
+                            };
+                        }
+                        stream.get_stream().try_read(&mut peeked[..control_len])?;
+                        idle_deadline = idle_timeout.map(|timeout: ::std::time::Duration| ::hyperlane::tokio::time::Instant::now() + timeout);
+                    }
+                    Event::Outbox(frame_list) => stream.try_send_list(frame_list).await?,
+                    Event::Timer => {
+                        let now: ::hyperlane::tokio::time::Instant = ::hyperlane::tokio::time::Instant::now();
+                        if idle_deadline.is_some_and(|deadline: ::hyperlane::tokio::time::Instant| now >= deadline) {
+                            let _: ::std::result::Result<(), ::hyperlane::ResponseError> = stream.try_send(::hyperlane::CLOSE_FRAME).await;
+                            stream.set_closed(true);
+                            return Err(::hyperlane::RequestError::ReadTimeout(::hyperlane::HttpStatus::RequestTimeout));
+                        }
+                        if let (::std::option::Option::Some(interval), ::std::option::Option::Some(ping)) = (ping_interval, next_ping) {
+                            if now >= ping {
+                                stream.try_send(::hyperlane::PING_FRAME).await?;
+                                next_ping = ::std::option::Option::Some(now + interval);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Keeps the error that ends the generated loop for the `on_error` handler.
+///
+/// The read expression records its error before the loop stops, and the handler runs after
+/// the loop with the error bound to its pattern. A loop left through `break` has no error.
+///
+/// # Arguments
+///
+/// - `TokenStream2` - The read expression evaluating to a `Result`.
+/// - `Option<&(Pat, Expr)>` - The optional error pattern and handler body.
+///
+/// # Returns
+///
+/// - `(TokenStream2, TokenStream2, TokenStream2)` - The setup before the loop, the read
+///   expression and the handler after the loop.
+fn capture_loop_error(
+    read: proc_macro2::TokenStream,
+    on_error: Option<&(Pat, Expr)>,
+) -> (
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+) {
+    let Some((pattern, body)) = on_error else {
+        return (quote! {}, read, quote! {});
+    };
+    (
+        quote! {
+            let mut loop_error: ::std::option::Option<::hyperlane::RequestError> = None;
+        },
+        quote! {
+            match #read {
+                Ok(value) => Ok(value),
+                Err(error) => {
+                    loop_error = Some(error);
+                    Err(())
+                }
+            }
+        },
+        quote! {
+            if let ::std::option::Option::Some(loop_error) = loop_error {
+                let #pattern = loop_error;
+                #body;
+            }
+        },
+    )
+}
+
+/// Runs the `on_close` cleanup once the generated loop has ended.
+///
+/// The loop runs inside an async block so that an early `return` from the function body
+/// also reaches the cleanup. A block is inlined and any other expression is called as an
+/// async function with the stream and the context.
+///
+/// # Arguments
+///
+/// - `&Ident` - The stream identifier.
+/// - `&Ident` - The context identifier.
+/// - `Option<&Expr>` - The optional cleanup block or function.
+/// - `TokenStream2` - The loop code evaluating to a `Status`.
+///
+/// # Returns
+///
+/// - `TokenStream2` - The loop code followed by the cleanup.
+fn wrap_on_close(
+    stream: &Ident,
+    context: &Ident,
+    on_close: Option<&Expr>,
+    loop_stream: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let cleanup: proc_macro2::TokenStream = match on_close {
+        None => return loop_stream,
+        Some(Expr::Block(block)) => quote! {
+            #block
+        },
+        Some(function) => quote! {
+            (#function)(&mut *#stream, &mut *#context).await;
+        },
+    };
+    quote! {
+        let loop_status: ::hyperlane::Status = async {
+            #loop_stream
+        }
+        .await;
+        #cleanup
+        loop_status
+    }
+}
+
 /// Generates stream processing loop for HTTP stream.
 ///
-/// Creates a `while let Ok(request)` loop that reads HTTP requests from the stream,
-/// sets the request on the context, and executes the function body statements.
+/// Creates a loop that reads HTTP requests from the stream, sets the request on the
+/// context, and executes the function body statements. With `max_requests`,
+/// `idle_timeout` or `on_close` given, the loop also stops after a request
+/// asking to close the connection, an HTTP/1.0 request without keep-alive, or the
+/// `max_requests`-th request, setting `Connection: close` on its response first.
+/// An `idle_timeout` ends the loop when the next request does not arrive in time, an
+/// `on_error` handler receives the error that ended the loop, and an `on_close` cleanup
+/// runs once after the loop.
 ///
 /// # Arguments
 ///
@@ -22,32 +231,104 @@ pub(crate) fn generate_http_stream(
     stmts: &[Stmt],
 ) -> proc_macro2::TokenStream {
     let method_ident: Ident = Ident::new("try_get_http_request", Span::call_site());
-    match data.variable_name.clone() {
-        Some(variable_name) => {
-            quote! {
-                while let Ok(#variable_name) = #stream.#method_ident().await {
-                    #context.set_request(#variable_name.clone());
-                    #(#stmts)*
-                }
-                ::hyperlane::Status::Continue
+    let read: proc_macro2::TokenStream = match data.idle_timeout_millis {
+        Some(idle_timeout_millis) => quote! {
+            match ::hyperlane::tokio::time::timeout(
+                ::std::time::Duration::from_millis(#idle_timeout_millis),
+                #stream.#method_ident(),
+            )
+            .await
+            {
+                Ok(result) => result,
+                Err(_) => Err(::hyperlane::RequestError::ReadTimeout(::hyperlane::HttpStatus::RequestTimeout)),
             }
-        }
-        None => {
-            quote! {
-                while let Ok(_request) = #stream.#method_ident().await {
-                    #context.set_request(_request);
-                    #(#stmts)*
-                }
-                ::hyperlane::Status::Continue
+        },
+        None => quote! {
+            #stream.#method_ident().await
+        },
+    };
+    let (error_setup, read, error_handler) = capture_loop_error(read, data.on_error.as_ref());
+    let (request, set_request): (proc_macro2::TokenStream, proc_macro2::TokenStream) =
+        match data.variable_name.clone() {
+            Some(variable_name) => (
+                quote! { #variable_name },
+                quote! { #context.set_request(#variable_name.clone()); },
+            ),
+            None => (
+                quote! { _request },
+                quote! { #context.set_request(_request); },
+            ),
+        };
+    if data.max_requests.is_none() && data.idle_timeout_millis.is_none() && data.on_close.is_none()
+    {
+        return quote! {
+            #error_setup
+            while let Ok(#request) = #read {
+                #set_request
+                #(#stmts)*
             }
-        }
+            #error_handler
+            ::hyperlane::Status::Continue
+        };
     }
+    let max_requests: proc_macro2::TokenStream = match data.max_requests {
+        Some(max_requests) => quote! {
+            ::std::option::Option::Some(#max_requests)
+        },
+        None => quote! {
+            ::std::option::Option::None::<usize>
+        },
+    };
+    let loop_stream: proc_macro2::TokenStream = quote! {
+        #error_setup
+        let mut keep_alive: bool = true;
+        let mut request_count: usize = 0;
+        loop {
+            if !keep_alive {
+                #stream.set_closed(true);
+                break;
+            }
+            let Ok(#request) = (#read) else {
+                break;
+            };
+            #set_request
+            request_count += 1;
+            keep_alive = {
+                let request: &::hyperlane::Request = #context.get_request();
+                let connection: ::std::string::String = request
+                    .try_get_header_back(::hyperlane::CONNECTION)
+                    .map(|connection| connection.to_ascii_lowercase())
+                    .unwrap_or_default();
+                let has_token = |token: &str| connection.split(',').any(|value: &str| value.trim() == token);
+                let is_persistent: bool = if request.get_version().is_http1_0() {
+                    has_token(::hyperlane::KEEP_ALIVE)
+                } else {
+                    !has_token(::hyperlane::CLOSE)
+                };
+                is_persistent && #max_requests.is_none_or(|max_requests: usize| request_count < max_requests)
+            };
+            if !keep_alive {
+                #context
+                    .get_mut_response()
+                    .set_header(::hyperlane::CONNECTION, ::hyperlane::CLOSE);
+            }
+            #(#stmts)*
+        }
+        #error_handler
+        ::hyperlane::Status::Continue
+    };
+    wrap_on_close(stream, context, data.on_close.as_ref(), loop_stream)
 }
 
 /// Generates stream processing loop for WebSocket stream.
 ///
 /// Creates a `while let Ok(body)` loop that reads WebSocket data from the stream,
 /// sets the body on the context's request, and executes the function body statements.
+/// With a message type each frame is decoded as JSON, and frames that fail to decode are
+/// handled by the `on_decode_error` action instead of reaching the statements.
+/// A `ping_interval` or `idle_timeout` reads through the heartbeat helper, an `on_error`
+/// handler receives the error that ended the loop, and an `on_close` cleanup runs once
+/// after the loop. When the function joins rooms the loop also delivers their broadcasts.
 ///
 /// # Arguments
 ///
@@ -55,6 +336,7 @@ pub(crate) fn generate_http_stream(
 /// - `&Ident` - The context identifier to use for setting the request body.
 /// - `&FromStreamData` - The FromStreamData containing variable name.
 /// - `&[Stmt]` - The statements to execute when data is successfully read.
+/// - `bool` - Whether `ws_join` binds a `ws_room_outbox` before the loop.
 ///
 /// # Returns
 ///
@@ -64,28 +346,122 @@ pub(crate) fn generate_websocket_stream(
     context: &Ident,
     data: &FromStreamData,
     stmts: &[Stmt],
+    has_rooms: bool,
 ) -> proc_macro2::TokenStream {
     let method_ident: Ident = Ident::new("try_get_websocket_request", Span::call_site());
-    match data.variable_name.clone() {
-        Some(variable_name) => {
+    let (setup, read): (proc_macro2::TokenStream, proc_macro2::TokenStream) = if data
+        .ping_interval_millis
+        .is_some()
+        || data.idle_timeout_millis.is_some()
+        || has_rooms
+    {
+        let helper: proc_macro2::TokenStream = websocket_reader_helper();
+        let ping_interval: proc_macro2::TokenStream = optional_duration(data.ping_interval_millis);
+        let idle_timeout: proc_macro2::TokenStream = optional_duration(data.idle_timeout_millis);
+        let outbox: proc_macro2::TokenStream = if has_rooms {
             quote! {
-                while let Ok(#variable_name) = #stream.#method_ident().await {
-                    #context.get_mut_request().set_body(#variable_name.clone());
-                    #(#stmts)*
-                }
-                ::hyperlane::Status::Continue
+                ::std::option::Option::Some(&mut ws_room_outbox)
             }
-        }
-        None => {
+        } else {
+            quote! {
+                ::std::option::Option::None
+            }
+        };
+        (
+            quote! {
+                #helper
+            },
+            quote! {
+                read_websocket_message(&mut *#stream, #ping_interval, #idle_timeout, #outbox).await
+            },
+        )
+    } else {
+        (
+            quote! {},
+            quote! {
+                #stream.#method_ident().await
+            },
+        )
+    };
+    let (error_setup, read, error_handler) = capture_loop_error(read, data.on_error.as_ref());
+    let loop_body: proc_macro2::TokenStream = match (&data.variable_name, &data.message_type) {
+        (Some(variable_name), Some(message_type)) => {
+            let on_decode_error: proc_macro2::TokenStream = match &data.on_decode_error {
+                DecodeErrorAction::Skip => quote! {
+                    continue;
+                },
+                DecodeErrorAction::Close => {
+                    let close_frame: [u8; 4] = WS_CLOSE_INVALID_PAYLOAD_FRAME;
+                    quote! {
+                        let _: ::std::result::Result<(), ::hyperlane::ResponseError> = #stream.try_send([#(#close_frame),*]).await;
+                        #stream.set_closed(true);
+                        break;
+                    }
+                }
+                DecodeErrorAction::Respond(frame) => quote! {
+                    let frame = #frame;
+                    let frame_list: ::std::vec::Vec<::hyperlane::ResponseBody> =
+                        ::hyperlane::WebSocketFrame::create_frame_list(&frame);
+                    if #stream.try_send_list(frame_list).await.is_err() {
+                        break;
+                    }
+                    continue;
+                },
+            };
             quote! {
-                while let Ok(_body) = #stream.#method_ident().await {
+                while let Ok(_body) = #read {
                     #context.get_mut_request().set_body(_body);
+                    let #variable_name: #message_type = match ::hyperlane::serde_json::from_slice::<#message_type>(#context.get_request().get_body()) {
+                        Ok(message) => message,
+                        #[allow(unused_variables)]
+                        Err(decode_error) => {
+                            #on_decode_error
+                        }
+                    };
                     #(#stmts)*
                 }
-                ::hyperlane::Status::Continue
             }
         }
+        (Some(variable_name), None) => quote! {
+            while let Ok(#variable_name) = #read {
+                #context.get_mut_request().set_body(#variable_name.clone());
+                #(#stmts)*
+            }
+        },
+        (None, _) => quote! {
+            while let Ok(_body) = #read {
+                #context.get_mut_request().set_body(_body);
+                #(#stmts)*
+            }
+        },
+    };
+    let loop_stream: proc_macro2::TokenStream = quote! {
+        #setup
+        #error_setup
+        #loop_body
+        #error_handler
+        ::hyperlane::Status::Continue
+    };
+    wrap_on_close(stream, context, data.on_close.as_ref(), loop_stream)
+}
+
+/// Rejects the options that only apply to the HTTP request loop.
+///
+/// # Arguments
+///
+/// - `&FromStreamData` - The parsed loop options.
+///
+/// # Returns
+///
+/// - `syn::Result<()>` - An error when `max_requests` is set.
+pub(crate) fn validate_websocket_stream(data: &FromStreamData) -> syn::Result<()> {
+    if data.max_requests.is_some() {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`max_requests` is only supported by `try_get_http_request`",
+        ));
     }
+    Ok(())
 }
 
 /// Wraps function body with HTTP stream processing.
@@ -105,6 +481,22 @@ pub(crate) fn generate_websocket_stream(
 /// - `TokenStream` - The expanded token stream with HTTP stream processing.
 pub(crate) fn try_get_http_request_macro(attr: TokenStream, item: TokenStream) -> TokenStream {
     let data: FromStreamData = parse_macro_input!(attr as FromStreamData);
+    if let Some(message_type) = &data.message_type {
+        return syn::Error::new_spanned(
+            message_type,
+            "typed messages are only supported by `try_get_websocket_request`",
+        )
+        .to_compile_error()
+        .into();
+    }
+    if data.ping_interval_millis.is_some() {
+        return syn::Error::new(
+            Span::call_site(),
+            "`ping_interval` is only supported by `try_get_websocket_request`",
+        )
+        .to_compile_error()
+        .into();
+    }
     let input_fn: ItemFn = parse_macro_input!(item as ItemFn);
     let vis: &Visibility = &input_fn.vis;
     let sig: &Signature = &input_fn.sig;
@@ -147,6 +539,9 @@ pub(crate) fn try_get_http_request_macro(attr: TokenStream, item: TokenStream) -
 /// - `TokenStream` - The expanded token stream with WebSocket stream processing.
 pub(crate) fn try_get_websocket_request_macro(attr: TokenStream, item: TokenStream) -> TokenStream {
     let data: FromStreamData = parse_macro_input!(attr as FromStreamData);
+    if let Err(err) = validate_websocket_stream(&data) {
+        return err.to_compile_error().into();
+    }
     let input_fn: ItemFn = parse_macro_input!(item as ItemFn);
     let vis: &Visibility = &input_fn.vis;
     let sig: &Signature = &input_fn.sig;
@@ -156,8 +551,9 @@ pub(crate) fn try_get_websocket_request_macro(attr: TokenStream, item: TokenStre
         Ok(stream) => match parse_context_from_signature(sig) {
             Ok(context) => {
                 let stmts: &Vec<Stmt> = &block.stmts;
+                let has_rooms: bool = has_attribute(attrs, WS_JOIN_MACRO);
                 let loop_stream: proc_macro2::TokenStream =
-                    generate_websocket_stream(&stream, &context, &data, stmts);
+                    generate_websocket_stream(&stream, &context, &data, stmts, has_rooms);
                 quote! {
                     #(#attrs)*
                     #vis #sig {
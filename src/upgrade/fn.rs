@@ -1,24 +1,49 @@
 use super::*;
 
+/// Generates the boolean expression checking the request upgrade type.
+///
+/// # Arguments
+///
+/// - `&Ident` - The context identifier.
+/// - `&proc_macro2::Ident` - The protocol upgrade type identifier as an ident.
+///
+/// # Returns
+///
+/// - `TokenStream2` - An expression that is `true` when the request upgrade type matches.
+pub(crate) fn protocol_condition(
+    context: &Ident,
+    upgrade_type: &proc_macro2::Ident,
+) -> proc_macro2::TokenStream {
+    let check_fn: proc_macro2::Ident =
+        Ident::new(&format!("is_{upgrade_type}"), upgrade_type.span());
+    quote! {
+        #context.get_request().get_upgrade_type().#check_fn()
+    }
+}
+
 /// Creates a protocol check function for protocol upgrade type validation.
 ///
 /// # Arguments
 ///
 /// - `&proc_macro2::Ident` - The protocol upgrade type identifier as an ident.
 ///
+/// - `Option<GuardResponse>` - The optional response sent when the check fails.
+///
 /// # Returns
 ///
 /// Returns a closure that generates the protocol check code.
 pub(crate) fn create_protocol_check(
     upgrade_type: &proc_macro2::Ident,
+    response: Option<GuardResponse>,
 ) -> impl FnOnce(&Ident, &Ident) -> proc_macro2::TokenStream {
-    let upgrade_type_str: String = upgrade_type.to_string();
-    move |context: &Ident, _: &Ident| {
-        let check_fn: proc_macro2::Ident =
-            Ident::new(&format!("is_{upgrade_type_str}"), context.span());
+    let upgrade_type: proc_macro2::Ident = upgrade_type.clone();
+    move |context: &Ident, stream: &Ident| {
+        let early_return: proc_macro2::TokenStream =
+            guard_return(context, stream, response.as_ref());
+        let condition: proc_macro2::TokenStream = protocol_condition(context, &upgrade_type);
         quote! {
-            if !#context.get_request().get_upgrade_type().#check_fn() {
-                return ::hyperlane::Status::Continue;
+            if !#condition {
+                #early_return
             }
         }
     }
@@ -36,15 +61,22 @@ pub(crate) fn create_protocol_check(
 /// - `$upgrade_type` - The protocol upgrade type identifier as an ident.
 macro_rules! impl_protocol_check_macro {
     ($name:ident, $submit_name:ident, $upgrade_type:ident) => {
-        pub(crate) fn $name(item: TokenStream, position: Position) -> TokenStream {
-            inject(
-                position,
-                item,
-                create_protocol_check(&proc_macro2::Ident::new(
-                    stringify!($upgrade_type),
-                    Span::call_site(),
-                )),
-            )
+        pub(crate) fn $name(
+            attr: TokenStream,
+            item: TokenStream,
+            position: Position,
+        ) -> TokenStream {
+            match parse_guard_response(attr) {
+                Ok(response) => inject(
+                    position,
+                    item,
+                    create_protocol_check(
+                        &proc_macro2::Ident::new(stringify!($upgrade_type), Span::call_site()),
+                        response,
+                    ),
+                ),
+                Err(err) => err.to_compile_error().into(),
+            }
         }
     };
 }
@@ -57,3 +89,189 @@ impl_protocol_check_macro!(
     is_unknown_upgrade_type,
     unknown
 );
+
+/// Validates a literal WebSocket subprotocol name.
+///
+/// The name must be a non-empty HTTP token, so it cannot contain whitespace, commas or
+/// other separators.
+///
+/// # Arguments
+///
+/// - `&Expr` - The subprotocol expression.
+///
+/// # Returns
+///
+/// - `syn::Result<()>` - An error spanned at the subprotocol when a literal name is malformed.
+pub(crate) fn validate_ws_protocol(protocol: &Expr) -> syn::Result<()> {
+    let Expr::Lit(ExprLit {
+        lit: Lit::Str(value),
+        ..
+    }) = protocol
+    else {
+        return Ok(());
+    };
+    let value: String = value.value();
+    let is_valid: bool = !value.is_empty()
+        && value
+            .bytes()
+            .all(|byte: u8| byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte));
+    if !is_valid {
+        return Err(syn::Error::new_spanned(
+            protocol,
+            "expected a subprotocol name without whitespace or separators, such as \"graphql-ws\"",
+        ));
+    }
+    Ok(())
+}
+
+/// Completes the WebSocket opening handshake.
+///
+/// The request must be a `GET` carrying `Upgrade: websocket`, a `Connection` header with the
+/// `upgrade` token, a well-formed `Sec-WebSocket-Key` and `Sec-WebSocket-Version: 13`.
+/// A valid handshake is answered with `101 Switching Protocols` and the matching
+/// `Sec-WebSocket-Accept`, after which the function body runs. A malformed handshake is
+/// answered with `400 Bad Request` unless another response is given, and an unsupported
+/// version additionally advertises `Sec-WebSocket-Version: 13`.
+///
+/// With `protocols(...)` the first listed subprotocol offered in `Sec-WebSocket-Protocol`
+/// is chosen, echoed in the handshake and bound to the `capture` variable, which defaults
+/// to `negotiated_protocol`. A client offering none of them is rejected with
+/// `400 Bad Request` and an explanatory body unless another response is given.
+/// Offered extensions are declined by leaving `Sec-WebSocket-Extensions` out of the handshake.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream with the WebSocket handshake.
+pub(crate) fn ws_upgrade_macro(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+) -> TokenStream {
+    let ws_upgrade_data: WsUpgradeData = parse_macro_input!(attr as WsUpgradeData);
+    let default_response: GuardResponse = GuardResponse {
+        status_code: parse_quote!(#BAD_REQUEST_STATUS_CODE),
+        body: None,
+    };
+    let default_protocol_response: GuardResponse = GuardResponse {
+        status_code: parse_quote!(#BAD_REQUEST_STATUS_CODE),
+        body: Some(parse_quote!(#WS_UNSUPPORTED_PROTOCOL_BODY)),
+    };
+    let response: &GuardResponse = ws_upgrade_data
+        .response
+        .as_ref()
+        .unwrap_or(&default_response);
+    let protocol_response: &GuardResponse = ws_upgrade_data
+        .response
+        .as_ref()
+        .unwrap_or(&default_protocol_response);
+    let protocols: &[Expr] = &ws_upgrade_data.protocols;
+    let capture: Ident = ws_upgrade_data
+        .capture
+        .clone()
+        .unwrap_or_else(|| Ident::new("negotiated_protocol", Span::call_site()));
+    inject(position, item, |context: &Ident, stream: &Ident| {
+        let new_context: proc_macro2::TokenStream = leak_mut_context(false, context);
+        let early_return: proc_macro2::TokenStream = guard_return(context, stream, Some(response));
+        let (negotiation, protocol_header): (proc_macro2::TokenStream, proc_macro2::TokenStream) =
+            if protocols.is_empty() {
+                (quote! {}, quote! {})
+            } else {
+                let protocol_return: proc_macro2::TokenStream =
+                    guard_return(context, stream, Some(protocol_response));
+                (
+                    quote! {
+                        let offered_protocols: ::std::vec::Vec<::std::string::String> = #context
+                            .get_request()
+                            .try_get_header(::hyperlane::SEC_WEBSOCKET_PROTOCOL)
+                            .map(|values| {
+                                values
+                                    .iter()
+                                    .flat_map(|value| value.split(','))
+                                    .map(|protocol: &str| protocol.trim().to_owned())
+                                    .filter(|protocol: &::std::string::String| !protocol.is_empty())
+                                    .collect()
+                            })
+                            .unwrap_or_default();
+                        let mut negotiated: ::std::option::Option<::std::string::String> = None;
+                        #(
+                            if negotiated.is_none() {
+                                let protocol = #protocols;
+                                let protocol: &str = ::std::convert::AsRef::<str>::as_ref(&protocol);
+                                if offered_protocols.iter().any(|offered| offered == protocol) {
+                                    negotiated = Some(protocol.to_owned());
+                                }
+                            }
+                        )*
+                        let ::std::option::Option::Some(#capture) = negotiated else {
+                            #protocol_return
+                        };
+                    },
+                    quote! {
+                        .set_header(::hyperlane::SEC_WEBSOCKET_PROTOCOL, &#capture)
+                    },
+                )
+            };
+        quote! {
+            let (websocket_key, is_supported_version): (::std::option::Option<::std::string::String>, bool) = {
+                let request: &::hyperlane::Request = #context.get_request();
+                let is_upgrade: bool = request.get_method().is_get()
+                    && request.get_upgrade_type().is_ws()
+                    && request
+                        .try_get_header_back(::hyperlane::CONNECTION)
+                        .is_some_and(|connection| {
+                            connection
+                                .split(',')
+                                .any(|token: &str| token.trim().eq_ignore_ascii_case(::hyperlane::UPGRADE))
+                        });
+                let websocket_key: ::std::option::Option<::std::string::String> = request
+                    .try_get_header_back(::hyperlane::SEC_WEBSOCKET_KEY)
+                    .map(|key| key.trim().to_owned())
+                    .filter(|key: &::std::string::String| {
+                        key.len() == 24
+                            && key.ends_with("==")
+                            && key[..22].bytes().all(|byte: u8| {
+                                byte.is_ascii_alphanumeric() || byte == b'+' || byte == b'/'
+                            })
+                    });
+                let is_supported_version: bool = request
+                    .try_get_header_back(::hyperlane::SEC_WEBSOCKET_VERSION)
+                    .is_some_and(|version| version.trim() == #WEBSOCKET_VERSION);
+                (websocket_key.filter(|_| is_upgrade), is_supported_version)
+            };
+            if !is_supported_version {
+                #new_context
+                    .get_mut_response()
+                    .set_header(::hyperlane::SEC_WEBSOCKET_VERSION, #WEBSOCKET_VERSION);
+            }
+            let (::std::option::Option::Some(websocket_key), true) = (websocket_key, is_supported_version) else {
+                #early_return
+            };
+            #negotiation
+            {
+                let status_code: ::hyperlane::ResponseStatusCode =
+                    ::hyperlane::ResponseStatusCode::from(#SWITCHING_PROTOCOLS_STATUS_CODE);
+                let response: &mut ::hyperlane::Response = #new_context.get_mut_response();
+                response
+                    .set_status_code(status_code)
+                    .set_reason_phrase(::hyperlane::HttpStatus::phrase(status_code))
+                    .set_header(::hyperlane::UPGRADE, ::hyperlane::WEBSOCKET)
+                    .set_header(::hyperlane::CONNECTION, ::hyperlane::UPGRADE)
+                    .set_header(
+                        ::hyperlane::SEC_WEBSOCKET_ACCEPT,
+                        &::hyperlane::WebSocketFrame::generate_accept_key(&websocket_key),
+                    )
+                    #protocol_header
+                    .set_body(::hyperlane::ResponseBody::new());
+                if #stream.try_send(response.build()).await.is_err() {
+                    return ::hyperlane::Status::Reject;
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,84 @@
+use super::*;
+
+/// Implementation of Parse trait for WsUpgradeData.
+///
+/// Parses an optional `protocols("name", ...)` list, a `capture = variable` option
+/// and a `respond = ...` or `else = ...` option.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<WsUpgradeData>` - Parsed WsUpgradeData or error.
+impl Parse for WsUpgradeData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut protocols: Option<Vec<Expr>> = None;
+        let mut capture: Option<Ident> = None;
+        let mut response: Option<GuardResponse> = None;
+        while !input.is_empty() {
+            if peek_guard_response(input) {
+                if response.is_some() {
+                    return Err(input.error("expected at most one `respond` or `else` option"));
+                }
+                response = Some(input.parse()?);
+            } else {
+                let name: Ident = input.parse()?;
+                if name == WS_UPGRADE_PROTOCOLS_OPTION && input.peek(token::Paren) {
+                    if protocols.is_some() {
+                        return Err(syn::Error::new(
+                            name.span(),
+                            "expected at most one `protocols(...)` option",
+                        ));
+                    }
+                    let content;
+                    parenthesized!(content in input);
+                    let list: Punctuated<Expr, Token![,]> = Punctuated::parse_terminated(&content)?;
+                    if list.is_empty() {
+                        return Err(syn::Error::new(
+                            name.span(),
+                            "expected at least one subprotocol in `protocols(...)`",
+                        ));
+                    }
+                    for protocol in list.iter() {
+                        validate_ws_protocol(protocol)?;
+                    }
+                    protocols = Some(list.into_iter().collect());
+                } else if name == CAPTURE_OPTION && input.peek(Token![=]) {
+                    if capture.is_some() {
+                        return Err(syn::Error::new(
+                            name.span(),
+                            "expected at most one `capture` option",
+                        ));
+                    }
+                    input.parse::<Token![=]>()?;
+                    capture = Some(input.parse()?);
+                } else {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        "expected `protocols(...)`, `capture = variable`, `respond = ...` or `else = ...`",
+                    ));
+                }
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        let protocols: Vec<Expr> = protocols.unwrap_or_default();
+        if let Some(capture) = &capture
+            && protocols.is_empty()
+        {
+            return Err(syn::Error::new(
+                capture.span(),
+                "`capture` requires a `protocols(...)` option",
+            ));
+        }
+        Ok(WsUpgradeData {
+            protocols,
+            capture,
+            response,
+        })
+    }
+}
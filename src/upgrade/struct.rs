@@ -0,0 +1,14 @@
+use super::*;
+
+/// WebSocket handshake data container for the `ws_upgrade` macro.
+///
+/// Stores the supported subprotocols, the optional variable bound to the negotiated
+/// subprotocol and the optional response sent when the handshake is rejected.
+pub(crate) struct WsUpgradeData {
+    /// Vector of supported subprotocol expressions in order of preference.
+    pub(crate) protocols: Vec<Expr>,
+    /// The optional variable bound to the negotiated subprotocol.
+    pub(crate) capture: Option<Ident>,
+    /// The optional response sent when the handshake is rejected.
+    pub(crate) response: Option<GuardResponse>,
+}
@@ -1,23 +1,48 @@
 use super::*;
 
+/// Generates the boolean expression checking the request HTTP version.
+///
+/// # Arguments
+///
+/// - `&Ident` - The context identifier.
+/// - `&proc_macro2::Ident` - The HTTP version identifier as an ident.
+///
+/// # Returns
+///
+/// - `TokenStream2` - An expression that is `true` when the request version matches.
+pub(crate) fn version_condition(
+    context: &Ident,
+    version: &proc_macro2::Ident,
+) -> proc_macro2::TokenStream {
+    let check_fn: proc_macro2::Ident = Ident::new(&format!("is_{version}"), version.span());
+    quote! {
+        #context.get_request().get_version().#check_fn()
+    }
+}
+
 /// Creates a version check function for HTTP version validation.
 ///
 /// # Arguments
 ///
 /// - `&proc_macro2::Ident` - The HTTP version identifier as an ident.
 ///
+/// - `Option<GuardResponse>` - The optional response sent when the check fails.
+///
 /// # Returns
 ///
 /// Returns a closure that generates the version check code.
 pub(crate) fn create_version_check(
     version: &proc_macro2::Ident,
+    response: Option<GuardResponse>,
 ) -> impl FnOnce(&Ident, &Ident) -> proc_macro2::TokenStream {
-    let version_str: String = version.to_string();
-    move |context: &Ident, _: &Ident| {
-        let check_fn: proc_macro2::Ident = Ident::new(&format!("is_{version_str}"), context.span());
+    let version: proc_macro2::Ident = version.clone();
+    move |context: &Ident, stream: &Ident| {
+        let early_return: proc_macro2::TokenStream =
+            guard_return(context, stream, response.as_ref());
+        let condition: proc_macro2::TokenStream = version_condition(context, &version);
         quote! {
-            if !#context.get_request().get_version().#check_fn() {
-                return ::hyperlane::Status::Continue;
+            if !#condition {
+                #early_return
             }
         }
     }
@@ -35,15 +60,22 @@ pub(crate) fn create_version_check(
 /// - `$version` - The HTTP version identifier as an ident.
 macro_rules! impl_version_check_macro {
     ($name:ident, $submit_name:ident, $version:ident) => {
-        pub(crate) fn $name(item: TokenStream, position: Position) -> TokenStream {
-            inject(
-                position,
-                item,
-                create_version_check(&proc_macro2::Ident::new(
-                    stringify!($version),
-                    Span::call_site(),
-                )),
-            )
+        pub(crate) fn $name(
+            attr: TokenStream,
+            item: TokenStream,
+            position: Position,
+        ) -> TokenStream {
+            match parse_guard_response(attr) {
+                Ok(response) => inject(
+                    position,
+                    item,
+                    create_version_check(
+                        &proc_macro2::Ident::new(stringify!($version), Span::call_site()),
+                        response,
+                    ),
+                ),
+                Err(err) => err.to_compile_error().into(),
+            }
         }
     };
 }
@@ -0,0 +1,94 @@
+use super::*;
+
+/// Declares a complete WebSocket endpoint on a struct.
+///
+/// Registers the struct as the route handler for the path and implements `ServerHook` for it.
+/// The generated `handle` is guarded by `is_ws_upgrade_type`, so requests that are not a
+/// WebSocket upgrade are rejected without a response. It then completes the handshake as
+/// `ws_upgrade` does, awaits `on_open`, and
+/// then runs the `try_get_websocket_request` loop with the given options, awaiting
+/// `on_message(msg, stream, ctx)` for each message and `on_close` once the loop ends.
+/// `on_open` and `on_close` default to doing nothing: the defaults come from a private trait,
+/// so inherent methods with the same names take precedence.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The route path and the loop options.
+/// - `TokenStream` - The struct token stream being decorated.
+///
+/// # Returns
+///
+/// - `TokenStream` - The struct, its route registration and its `ServerHook` implementation.
+pub(crate) fn websocket_macro(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let WebSocketData {
+        path,
+        stream: mut data,
+    } = parse_macro_input!(attr as WebSocketData);
+    let input_struct: ItemStruct = parse_macro_input!(item as ItemStruct);
+    if let Err(err) = validate_websocket_stream(&data) {
+        return err.to_compile_error().into();
+    }
+    if let Some(on_close) = &data.on_close {
+        return syn::Error::new_spanned(
+            on_close,
+            "define an `on_close` method instead of the `on_close` option",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let struct_name: &Ident = &input_struct.ident;
+    let stream: Ident = Ident::new("stream", Span::call_site());
+    let context: Ident = Ident::new("ctx", Span::call_site());
+    let message: Expr = data
+        .variable_name
+        .get_or_insert_with(|| parse_quote!(message))
+        .clone();
+    data.on_close = Some(parse_quote!({
+        self.on_close(#stream, #context).await;
+    }));
+    let stmts: Vec<Stmt> = vec![parse_quote! {
+        self.on_message(#message, #stream, #context).await;
+    }];
+    let loop_stream: proc_macro2::TokenStream =
+        generate_websocket_stream(&stream, &context, &data, &stmts, false);
+    let handle: TokenStream = quote! {
+        async fn handle(mut self, #stream: &mut ::hyperlane::Stream, #context: &mut ::hyperlane::Context) -> ::hyperlane::Status {
+            self.on_open(#stream, #context).await;
+            #loop_stream
+        }
+    }
+    .into();
+    let handle: TokenStream = ws_upgrade_macro(TokenStream::new(), handle, Position::Prologue);
+    let handle: proc_macro2::TokenStream =
+        is_ws_upgrade_type_macro(TokenStream::new(), handle, Position::Prologue).into();
+    let new_self: proc_macro2::TokenStream = match &input_struct.fields {
+        Fields::Unit => quote! {
+            Self
+        },
+        _ => quote! {
+            ::std::default::Default::default()
+        },
+    };
+    quote! {
+        #input_struct
+        ::hyperlane::inventory::submit! {
+            ::hyperlane::HookType::Route(#path, || ::hyperlane::Hook::factory::<#struct_name>())
+        }
+        const _: () = {
+            #[allow(dead_code)]
+            trait WebSocketHooks {
+                async fn on_open(&mut self, _: &mut ::hyperlane::Stream, _: &mut ::hyperlane::Context) {}
+                async fn on_close(&mut self, _: &mut ::hyperlane::Stream, _: &mut ::hyperlane::Context) {}
+            }
+            impl WebSocketHooks for #struct_name {}
+            impl ::hyperlane::ServerHook for #struct_name {
+                async fn new(_: &mut ::hyperlane::Stream, _: &mut ::hyperlane::Context) -> Self {
+                    #new_self
+                }
+
+                #handle
+            }
+        };
+    }
+    .into()
+}
@@ -0,0 +1,24 @@
+use super::*;
+
+/// Implementation of Parse trait for WebSocketData.
+///
+/// Parses the route path optionally followed by a comma and the options of
+/// `try_get_websocket_request`.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<WebSocketData>` - Parsed WebSocketData or error.
+impl Parse for WebSocketData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path: Expr = input.parse()?;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+        let stream: FromStreamData = input.parse()?;
+        Ok(WebSocketData { path, stream })
+    }
+}
@@ -0,0 +1,11 @@
+use super::*;
+
+/// Endpoint data for the `websocket` macro.
+///
+/// Stores the route path and the options of the receive loop.
+pub(crate) struct WebSocketData {
+    /// The path expression for the route.
+    pub(crate) path: Expr,
+    /// The options of the receive loop, as accepted by `try_get_websocket_request`.
+    pub(crate) stream: FromStreamData,
+}
@@ -0,0 +1,241 @@
+use super::*;
+
+/// Extracts the message type of a method routed to by `ws_dispatch`.
+///
+/// The method must be async, borrow `self` and take the message, the stream and the
+/// context, in that order.
+///
+/// # Arguments
+///
+/// - `&ImplItemFn` - The routed method.
+///
+/// # Returns
+///
+/// - `syn::Result<Type>` - The message type, or an error spanned at the signature.
+fn dispatch_message_type(method: &ImplItemFn) -> syn::Result<Type> {
+    let sig: &Signature = &method.sig;
+    let error = || {
+        syn::Error::new_spanned(
+            sig,
+            "expected `async fn name(&mut self, msg: Type, stream: &mut Stream, ctx: &mut Context)`",
+        )
+    };
+    if sig.asyncness.is_none() {
+        return Err(error());
+    }
+    let mut inputs = sig.inputs.iter();
+    match inputs.next() {
+        Some(FnArg::Receiver(receiver)) if receiver.reference.is_some() => {}
+        _ => return Err(error()),
+    }
+    let typed: Vec<&PatType> = inputs
+        .map(|input: &FnArg| match input {
+            FnArg::Typed(pat_type) => Ok(pat_type),
+            FnArg::Receiver(_) => Err(error()),
+        })
+        .collect::<syn::Result<_>>()?;
+    match typed.as_slice() {
+        [message, _, _] => Ok((*message.ty).clone()),
+        _ => Err(error()),
+    }
+}
+
+/// Routes WebSocket messages to methods by the type tag of their JSON envelope.
+///
+/// Each method marked `#[on("name")]` receives the messages whose tag field equals `name`,
+/// decoded into its message type after the tag field is removed. The method marked
+/// `#[fallback]` receives the whole envelope as a `serde_json::Value` when the tag is
+/// missing or matches no method. The generated `dispatch` method decodes a body and awaits
+/// the chosen method, failing with the `serde_json::Error` of a malformed message, or of an
+/// unmatched tag when there is no fallback.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The impl block to process.
+///
+/// # Returns
+///
+/// - `TokenStream` - The impl block with the `dispatch` method added.
+pub(crate) fn ws_dispatch_macro(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let ws_dispatch_data: WsDispatchData = parse_macro_input!(attr as WsDispatchData);
+    let mut item_impl: ItemImpl = parse_macro_input!(item as ItemImpl);
+    if let Some((_, path, _)) = &item_impl.trait_ {
+        return syn::Error::new_spanned(
+            path,
+            "`ws_dispatch` must be applied to an inherent impl block",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let tag: String = ws_dispatch_data
+        .tag
+        .map(|tag: LitStr| tag.value())
+        .unwrap_or_else(|| WS_DISPATCH_DEFAULT_TAG.to_owned());
+    let mut routes: Vec<(LitStr, WsDispatchRoute)> = Vec::new();
+    let mut fallback: Option<Ident> = None;
+    let mut output: Option<Type> = None;
+    for impl_item in item_impl.items.iter_mut() {
+        let ImplItem::Fn(method) = impl_item else {
+            continue;
+        };
+        let mut name: Option<LitStr> = None;
+        let mut is_fallback: bool = false;
+        let mut result: syn::Result<()> = Ok(());
+        method.attrs.retain(|attr: &Attribute| {
+            if attr.path().is_ident(WS_DISPATCH_ON_ATTRIBUTE) {
+                match attr.parse_args::<LitStr>() {
+                    Ok(value) if name.is_none() => name = Some(value),
+                    Ok(value) => {
+                        result = Err(syn::Error::new(
+                            value.span(),
+                            "expected at most one `on` attribute per method",
+                        ))
+                    }
+                    Err(err) => result = Err(err),
+                }
+                false
+            } else if attr.path().is_ident(WS_DISPATCH_FALLBACK_ATTRIBUTE) {
+                is_fallback = true;
+                false
+            } else {
+                true
+            }
+        });
+        if let Err(err) = result {
+            return err.to_compile_error().into();
+        }
+        if name.is_none() && !is_fallback {
+            continue;
+        }
+        if name.is_some() && is_fallback {
+            return syn::Error::new_spanned(
+                &method.sig.ident,
+                "a method cannot be marked both `on` and `fallback`",
+            )
+            .to_compile_error()
+            .into();
+        }
+        let message_type: Type = match dispatch_message_type(method) {
+            Ok(message_type) => message_type,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        let method_output: Type = match &method.sig.output {
+            ReturnType::Type(_, ty) => (**ty).clone(),
+            ReturnType::Default => parse_quote!(()),
+        };
+        match &output {
+            Some(output) if *output != method_output => {
+                let span: proc_macro2::TokenStream = match &method.sig.output {
+                    ReturnType::Type(_, ty) => quote!(#ty),
+                    ReturnType::Default => {
+                        let ident: &Ident = &method.sig.ident;
+                        quote!(#ident)
+                    }
+                };
+                return syn::Error::new_spanned(
+                    span,
+                    format!(
+                        "expected return type `{}` like the other routed methods",
+                        quote!(#output)
+                    ),
+                )
+                .to_compile_error()
+                .into();
+            }
+            Some(_) => {}
+            None => output = Some(method_output),
+        }
+        let method_ident: Ident = method.sig.ident.clone();
+        if let Some(name) = name {
+            if routes
+                .iter()
+                .any(|(route, _)| route.value() == name.value())
+            {
+                return syn::Error::new(name.span(), "duplicate message type")
+                    .to_compile_error()
+                    .into();
+            }
+            routes.push((
+                name,
+                WsDispatchRoute {
+                    method: method_ident.clone(),
+                    message_type,
+                },
+            ));
+        }
+        if is_fallback {
+            if fallback.is_some() {
+                return syn::Error::new(
+                    method_ident.span(),
+                    "expected at most one `fallback` method",
+                )
+                .to_compile_error()
+                .into();
+            }
+            fallback = Some(method_ident);
+        }
+    }
+    if routes.is_empty() {
+        return syn::Error::new(
+            Span::call_site(),
+            "expected at least one method marked `#[on(\"type\")]`",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let output: Type = output.unwrap_or_else(|| parse_quote!(()));
+    let arms = routes.iter().map(|(name, route)| {
+        let WsDispatchRoute {
+            method,
+            message_type,
+        } = route;
+        quote! {
+            ::std::option::Option::Some(#name) => {
+                if let ::std::option::Option::Some(fields) = envelope.as_object_mut() {
+                    fields.remove(#tag);
+                }
+                let message: #message_type = ::hyperlane::serde_json::from_value(envelope)?;
+                Ok(self.#method(message, stream, ctx).await)
+            }
+        }
+    });
+    let fallback_arm: proc_macro2::TokenStream = match fallback {
+        Some(fallback) => quote! {
+            _ => Ok(self.#fallback(envelope, stream, ctx).await),
+        },
+        None => quote! {
+            ::std::option::Option::Some(unknown) => Err(::hyperlane::serde_json::Error::io(::std::io::Error::new(
+                ::std::io::ErrorKind::InvalidData,
+                ::std::format!("unknown message type {unknown:?}"),
+            ))),
+            ::std::option::Option::None => Err(::hyperlane::serde_json::Error::io(::std::io::Error::new(
+                ::std::io::ErrorKind::InvalidData,
+                ::std::format!("missing message type field {:?}", #tag),
+            ))),
+        },
+    };
+    let dispatch: ImplItem = parse_quote! {
+        pub async fn dispatch(
+            &mut self,
+            body: &[u8],
+            stream: &mut ::hyperlane::Stream,
+            ctx: &mut ::hyperlane::Context,
+        ) -> ::hyperlane::serde_json::Result<#output> {
+            let mut envelope: ::hyperlane::serde_json::Value = ::hyperlane::serde_json::from_slice(body)?;
+            let tag: ::std::option::Option<::std::string::String> = envelope
+                .get(#tag)
+                .and_then(::hyperlane::serde_json::Value::as_str)
+                .map(::std::borrow::ToOwned::to_owned);
+            match tag.as_deref() {
+                #(#arms)*
+                #fallback_arm
+            }
+        }
+    };
+    item_impl.items.push(dispatch);
+    quote! {
+        #item_impl
+    }
+    .into()
+}
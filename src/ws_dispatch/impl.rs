@@ -0,0 +1,34 @@
+use super::*;
+
+/// Implementation of Parse trait for WsDispatchData.
+///
+/// Parses an optional `tag = "name"` option.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<WsDispatchData>` - Parsed WsDispatchData or error.
+impl Parse for WsDispatchData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Ok(WsDispatchData { tag: None });
+        }
+        let name: Ident = input.parse()?;
+        if name != WS_DISPATCH_TAG_OPTION {
+            return Err(syn::Error::new(name.span(), "expected `tag = \"name\"`"));
+        }
+        input.parse::<Token![=]>()?;
+        let tag: LitStr = input.parse()?;
+        if tag.value().is_empty() {
+            return Err(syn::Error::new(
+                tag.span(),
+                "expected a non-empty tag field name",
+            ));
+        }
+        input.parse::<Option<Token![,]>>()?;
+        Ok(WsDispatchData { tag: Some(tag) })
+    }
+}
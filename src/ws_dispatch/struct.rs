@@ -0,0 +1,19 @@
+use super::*;
+
+/// Dispatch options for the `ws_dispatch` macro.
+///
+/// Stores the optional name of the envelope field holding the message type.
+pub(crate) struct WsDispatchData {
+    /// The optional tag field name, `type` when omitted.
+    pub(crate) tag: Option<LitStr>,
+}
+
+/// A method routed to by the `ws_dispatch` macro.
+///
+/// Stores the method name and the type its message is decoded into.
+pub(crate) struct WsDispatchRoute {
+    /// The method name.
+    pub(crate) method: Ident,
+    /// The type of the message parameter.
+    pub(crate) message_type: Type,
+}
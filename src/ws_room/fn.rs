@@ -0,0 +1,217 @@
+use super::*;
+
+/// Generates the type of a room registry.
+///
+/// A registry pairs the counter issuing member ids with a map from each room to its
+/// members, keyed by member id, holding the sender feeding frames into each member's
+/// receive loop through a bounded queue.
+///
+/// # Returns
+///
+/// - `TokenStream2` - The registry type.
+fn ws_room_registry_type() -> proc_macro2::TokenStream {
+    quote! {
+        (
+            ::std::sync::atomic::AtomicU64,
+            ::std::sync::Mutex<
+                ::std::collections::HashMap<
+                    ::std::string::String,
+                    ::std::collections::HashMap<
+                        u64,
+                        ::hyperlane::tokio::sync::mpsc::Sender<::std::vec::Vec<::hyperlane::ResponseBody>>,
+                    >,
+                >,
+            >,
+        )
+    }
+}
+
+/// Declares a WebSocket room registry.
+///
+/// The registry is a lazily initialized static shared by every handler naming it in
+/// `ws_join`, `ws_broadcast` or `ws_room_size`.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The optional visibility and the registry name.
+///
+/// # Returns
+///
+/// - `TokenStream` - The registry static.
+pub(crate) fn ws_rooms_macro(input: TokenStream) -> TokenStream {
+    let WsRoomsData { vis, name } = parse_macro_input!(input as WsRoomsData);
+    let registry_type: proc_macro2::TokenStream = ws_room_registry_type();
+    quote! {
+        #vis static #name: ::std::sync::LazyLock<#registry_type> =
+            ::std::sync::LazyLock::new(::std::default::Default::default);
+    }
+    .into()
+}
+
+/// Joins the connection to rooms of a registry.
+///
+/// The connection takes the next member id of the registry, bound to `ws_room_member`.
+/// Each room is converted with `ToString` and the id is registered under it with
+/// the sending half of a bounded channel bound to `ws_room_outbox`. The `try_get_websocket_request`
+/// loop applied above this attribute writes the frames arriving on that channel to the
+/// stream while it waits for the next message. The membership is dropped when the function
+/// returns, which removes the connection from its rooms and deletes rooms left empty.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The attribute token stream.
+/// - `TokenStream` - The input token stream to process.
+/// - `Position` - The position to inject the code.
+///
+/// # Returns
+///
+/// - `TokenStream` - The expanded token stream joining the rooms.
+pub(crate) fn ws_join_macro(
+    attr: TokenStream,
+    item: TokenStream,
+    position: Position,
+) -> TokenStream {
+    let ws_join_data: WsJoinData = parse_macro_input!(attr as WsJoinData);
+    if let Ok(input_fn) = parse::<ItemFn>(item.clone())
+        && has_attribute(&input_fn.attrs, TRY_GET_WEBSOCKET_REQUEST_MACRO)
+    {
+        return syn::Error::new(
+            Span::call_site(),
+            "`ws_join` must be placed below `try_get_websocket_request`",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let registry: &Path = &ws_join_data.rooms[0].0;
+    if let Some((other, _)) = ws_join_data
+        .rooms
+        .iter()
+        .find(|(other, _)| other != registry)
+    {
+        return syn::Error::new_spanned(
+            other,
+            "expected every room of one `ws_join` to belong to the same registry",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let registry_type: proc_macro2::TokenStream = ws_room_registry_type();
+    inject(position, item, |_context: &Ident, _stream: &Ident| {
+        let joins = ws_join_data.rooms.iter().map(|(_, room)| {
+            quote! {
+                {
+                    let room: ::std::string::String = ::std::string::ToString::to_string(&#room);
+                    rooms
+                        .1
+                        .lock()
+                        .unwrap_or_else(::std::sync::PoisonError::into_inner)
+                        .entry(room.clone())
+                        .or_default()
+                        .insert(member, sender.clone());
+                    WsRoomMembership { rooms, room, member }
+                }
+            }
+        });
+        quote! {
+            #[allow(unused_mut, unused_variables)]
+            let (mut ws_room_outbox, ws_room_member, ws_room_memberships) = {
+                struct WsRoomMembership {
+                    rooms: &'static #registry_type,
+                    room: ::std::string::String,
+                    member: u64,
+                }
+                impl ::std::ops::Drop for WsRoomMembership {
+                    fn drop(&mut self) {
+                        let mut rooms = self.rooms.1.lock().unwrap_or_else(::std::sync::PoisonError::into_inner);
+                        if let ::std::option::Option::Some(members) = rooms.get_mut(&self.room) {
+                            members.remove(&self.member);
+                            if members.is_empty() {
+                                rooms.remove(&self.room);
+                            }
+                        }
+                    }
+                }
+                let rooms: &'static #registry_type = &#registry;
+                let member: u64 = rooms.0.fetch_add(1, ::std::sync::atomic::Ordering::Relaxed);
+                let (sender, receiver) =
+                    ::hyperlane::tokio::sync::mpsc::channel::<::std::vec::Vec<::hyperlane::ResponseBody>>(#WS_ROOM_OUTBOX_CAPACITY);
+                let memberships: ::std::vec::Vec<WsRoomMembership> = ::std::vec![#(#joins),*];
+                (receiver, member, memberships)
+            };
+        }
+    })
+}
+
+/// Sends a message to every member of a room.
+///
+/// The message is framed once and queued for each member, optionally skipping the
+/// member whose id is given by `exclude`. Each member queues a bounded number of messages;
+/// a member whose queue is full because it stopped reading misses the message instead of
+/// buffering it without limit. The expression evaluates to the number of members the
+/// message was queued for.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The registry, room, message and optional `exclude` option.
+///
+/// # Returns
+///
+/// - `TokenStream` - The broadcast expression.
+pub(crate) fn ws_broadcast_macro(input: TokenStream) -> TokenStream {
+    let WsBroadcastData {
+        registry,
+        room,
+        message,
+        exclude,
+    } = parse_macro_input!(input as WsBroadcastData);
+    let excluded: proc_macro2::TokenStream = match exclude {
+        Some(exclude) => quote! {
+            ::std::option::Option::Some(#exclude)
+        },
+        None => quote! {
+            ::std::option::Option::None
+        },
+    };
+    quote! {
+        {
+            let frame_list: ::std::vec::Vec<::hyperlane::ResponseBody> =
+                ::hyperlane::WebSocketFrame::create_frame_list(&#message);
+            let excluded: ::std::option::Option<u64> = #excluded;
+            let room: ::std::string::String = ::std::string::ToString::to_string(&#room);
+            let rooms = #registry.1.lock().unwrap_or_else(::std::sync::PoisonError::into_inner);
+            rooms.get(&room).map_or(0, |members| {
+                members
+                    .iter()
+                    .filter(|(member, _)| ::std::option::Option::Some(**member) != excluded)
+                    .filter(|(_, sender)| sender.try_send(frame_list.clone()).is_ok())
+                    .count()
+            })
+        }
+    }
+    .into()
+}
+
+/// Counts the members of a room.
+///
+/// # Arguments
+///
+/// - `TokenStream` - The registry and room.
+///
+/// # Returns
+///
+/// - `TokenStream` - A `usize` expression, zero for a room without members.
+pub(crate) fn ws_room_size_macro(input: TokenStream) -> TokenStream {
+    let WsRoomSizeData { registry, room } = parse_macro_input!(input as WsRoomSizeData);
+    quote! {
+        {
+            let room: ::std::string::String = ::std::string::ToString::to_string(&#room);
+            #registry
+                .1
+                .lock()
+                .unwrap_or_else(::std::sync::PoisonError::into_inner)
+                .get(&room)
+                .map_or(0, |members| members.len())
+        }
+    }
+    .into()
+}
@@ -0,0 +1,110 @@
+use super::*;
+
+/// Implementation of Parse trait for WsRoomsData.
+///
+/// Parses an optional visibility followed by the registry name.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<WsRoomsData>` - Parsed WsRoomsData or error.
+impl Parse for WsRoomsData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis: Visibility = input.parse()?;
+        let name: Ident = input.parse()?;
+        input.parse::<Option<Token![;]>>()?;
+        Ok(WsRoomsData { vis, name })
+    }
+}
+
+/// Implementation of Parse trait for WsJoinData.
+///
+/// Parses a comma-separated list of `REGISTRY => room` pairs.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<WsJoinData>` - Parsed WsJoinData or error.
+impl Parse for WsJoinData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut rooms: Vec<(Path, Expr)> = Vec::new();
+        while !input.is_empty() {
+            let registry: Path = input.parse()?;
+            input.parse::<Token![=>]>()?;
+            let room: Expr = input.parse()?;
+            rooms.push((registry, room));
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        if rooms.is_empty() {
+            return Err(input.error("expected at least one `REGISTRY => room` pair"));
+        }
+        Ok(WsJoinData { rooms })
+    }
+}
+
+/// Implementation of Parse trait for WsBroadcastData.
+///
+/// Parses `REGISTRY, room, message` followed by an optional `exclude = member` option.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<WsBroadcastData>` - Parsed WsBroadcastData or error.
+impl Parse for WsBroadcastData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let registry: Path = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let room: Expr = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let message: Expr = input.parse()?;
+        let mut exclude: Option<Expr> = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let name: Ident = input.parse()?;
+            if name != WS_BROADCAST_EXCLUDE_OPTION {
+                return Err(syn::Error::new(name.span(), "expected `exclude = member`"));
+            }
+            input.parse::<Token![=]>()?;
+            exclude = Some(input.parse()?);
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(WsBroadcastData {
+            registry,
+            room,
+            message,
+            exclude,
+        })
+    }
+}
+
+/// Implementation of Parse trait for WsRoomSizeData.
+///
+/// Parses `REGISTRY, room`.
+///
+/// # Arguments
+///
+/// - `ParseStream` - The input parse stream.
+///
+/// # Returns
+///
+/// - `syn::Result<WsRoomSizeData>` - Parsed WsRoomSizeData or error.
+impl Parse for WsRoomSizeData {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let registry: Path = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let room: Expr = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(WsRoomSizeData { registry, room })
+    }
+}
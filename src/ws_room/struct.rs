@@ -0,0 +1,43 @@
+use super::*;
+
+/// Room registry declaration for the `ws_rooms` macro.
+///
+/// Stores the visibility and the name of the generated registry static.
+pub(crate) struct WsRoomsData {
+    /// The visibility of the registry static.
+    pub(crate) vis: Visibility,
+    /// The name of the registry static.
+    pub(crate) name: Ident,
+}
+
+/// Room membership list for the `ws_join` macro.
+///
+/// Stores each registry paired with the room joined in it.
+pub(crate) struct WsJoinData {
+    /// Vector of registry paths and room expressions.
+    pub(crate) rooms: Vec<(Path, Expr)>,
+}
+
+/// Broadcast arguments for the `ws_broadcast` macro.
+///
+/// Stores the registry, the room, the message and the optional excluded member id.
+pub(crate) struct WsBroadcastData {
+    /// The registry path.
+    pub(crate) registry: Path,
+    /// The room expression.
+    pub(crate) room: Expr,
+    /// The message expression.
+    pub(crate) message: Expr,
+    /// The optional member id of the connection that does not receive the message.
+    pub(crate) exclude: Option<Expr>,
+}
+
+/// Member count arguments for the `ws_room_size` macro.
+///
+/// Stores the registry and the room to count.
+pub(crate) struct WsRoomSizeData {
+    /// The registry path.
+    pub(crate) registry: Path,
+    /// The room expression.
+    pub(crate) room: Expr,
+}